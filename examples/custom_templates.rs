@@ -23,9 +23,13 @@ fn main() {
     let formats = [
         (OutputFormat::Json, "JSON"),
         (OutputFormat::JsonPretty, "美化 JSON"),
+        (OutputFormat::Ndjson, "NDJSON"),
         (OutputFormat::Yaml, "YAML"),
         (OutputFormat::Csv, "CSV"),
+        (OutputFormat::Tsv, "TSV"),
         (OutputFormat::Table, "表格"),
+        (OutputFormat::Markdown, "Markdown"),
+        (OutputFormat::Minimap, "小地图"),
         (OutputFormat::Simple, "简单"),
         (OutputFormat::Detail, "详细"),
     ];
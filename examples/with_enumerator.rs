@@ -4,7 +4,7 @@
 #[cfg(feature = "window-enumerator")]
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     use window_enumerator::WindowEnumerator;
-    use window_enumerator_formatter::{OutputFormat, WindowListFormat};
+    use window_enumerator_formatter::{FormatConfig, OutputFormat, WindowListFormat};
 
     println!("=== 与 window-enumerator 集成示例 ===\n");
 
@@ -16,13 +16,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("找到 {} 个窗口\n", windows.len());
 
     // 显示前5个窗口的表格
-    if windows.len() > 5 {
-        println!("前5个窗口 (表格格式):");
-        println!("{}", windows[..5].format_with(OutputFormat::Table));
-    } else {
-        println!("所有窗口 (表格格式):");
-        println!("{}", windows.format_with(OutputFormat::Table));
-    }
+    let config = FormatConfig::builder()
+        .format(OutputFormat::Table)
+        .limit(5)
+        .build()?;
+    println!("前5个窗口 (表格格式):");
+    println!("{}", windows.format_output(&config));
 
     println!("\nJSON 格式示例:");
     if let Some(window) = windows.first() {
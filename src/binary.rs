@@ -0,0 +1,31 @@
+//! MessagePack and CBOR output.
+
+use crate::formatter::{OutputFormat, WindowFormatter};
+use serde::Serialize;
+use std::fmt::Write;
+
+impl WindowFormatter {
+    // Binary formats - records, page or snapshot envelope as maps with field names
+    pub(crate) fn encode_binary<T: Serialize + ?Sized>(value: &T, format: OutputFormat) -> Vec<u8> {
+        match format {
+            #[cfg(feature = "messagepack")]
+            OutputFormat::MessagePack => rmp_serde::to_vec_named(value).unwrap_or_default(),
+            #[cfg(feature = "cbor")]
+            OutputFormat::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes).map_or_else(|_| Vec::new(), |_| bytes)
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Lowercase hex dump, for binary output through the `String` API.
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+}
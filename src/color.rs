@@ -0,0 +1,129 @@
+//! ANSI color and styling for human-readable formats.
+
+use crate::formatter::WindowFormatter;
+use serde::{Deserialize, Serialize};
+use std::io::IsTerminal;
+
+/// When to color human-readable output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal, unless `NO_COLOR` is set.
+    /// `CLICOLOR_FORCE` (other than `0`) forces color on.
+    #[default]
+    Auto,
+    /// Always color.
+    Always,
+    /// Never color.
+    Never,
+}
+
+impl ColorChoice {
+    /// Resolve the choice against the environment and stdout.
+    pub fn should_color(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                let var = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty());
+                if var("CLICOLOR_FORCE").is_some_and(|v| v != "0") {
+                    true
+                } else if var("NO_COLOR").is_some() {
+                    false
+                } else {
+                    std::io::stdout().is_terminal()
+                }
+            }
+        }
+    }
+}
+
+/// SGR parameters for each styled element.
+pub(crate) struct Theme {
+    pub header: &'static str,
+    pub label: &'static str,
+    pub handle: &'static str,
+    pub pid: &'static str,
+    pub zero_size: &'static str,
+    pub key: &'static str,
+    pub string: &'static str,
+    pub number: &'static str,
+    pub literal: &'static str,
+    pub processes: &'static [&'static str],
+}
+
+pub(crate) const THEME: Theme = Theme {
+    header: "1",
+    label: "1",
+    handle: "2",
+    pid: "36",
+    zero_size: "90",
+    key: "34;1",
+    string: "32",
+    number: "36",
+    literal: "35",
+    processes: &[
+        "31", "32", "33", "34", "35", "91", "92", "93", "94", "95", "96",
+    ],
+};
+
+/// Map a style name used in templates (`{title|color:red}`, `{pid|bold}`) to SGR parameters.
+pub(crate) fn named_style(name: &str) -> Option<&'static str> {
+    let sgr = match name {
+        "bold" => "1",
+        "dim" => "2",
+        "italic" => "3",
+        "underline" => "4",
+        "black" => "30",
+        "red" => "31",
+        "green" => "32",
+        "yellow" => "33",
+        "blue" => "34",
+        "magenta" => "35",
+        "cyan" => "36",
+        "white" => "37",
+        "grey" | "gray" => "90",
+        "bright-red" => "91",
+        "bright-green" => "92",
+        "bright-yellow" => "93",
+        "bright-blue" => "94",
+        "bright-magenta" => "95",
+        "bright-cyan" => "96",
+        "bright-white" => "97",
+        _ => return None,
+    };
+    Some(sgr)
+}
+
+/// Applies styles when color is enabled and passes text through otherwise.
+#[derive(Clone, Copy)]
+pub(crate) struct Painter {
+    enabled: bool,
+}
+
+impl Painter {
+    pub(crate) fn new(choice: ColorChoice) -> Self {
+        Self {
+            enabled: choice.should_color(),
+        }
+    }
+
+    pub(crate) fn is_enabled(self) -> bool {
+        self.enabled
+    }
+
+    pub(crate) fn paint(self, text: &str, sgr: &str) -> String {
+        if self.enabled && !text.is_empty() {
+            format!("\x1b[{}m{}\x1b[0m", sgr, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    // Stable per-process color, so a process keeps its color across runs
+    pub(crate) fn process(self, text: &str, process_name: &str) -> String {
+        let palette = THEME.processes;
+        let index = WindowFormatter::stable_hash(process_name) % palette.len() as u64;
+        self.paint(text, palette[index as usize])
+    }
+}
@@ -0,0 +1,292 @@
+//! Loading [`FormatConfig`] from presets, config files and environment variables.
+
+use crate::error::FormatError;
+use crate::formatter::{FormatConfig, OutputFormat};
+use serde_json::{Map, Value};
+use std::path::Path;
+
+/// Prefix of the environment variables read by [`FormatConfig::from_env`].
+pub const ENV_PREFIX: &str = "WEF_";
+
+/// Environment variable naming the config file used by [`FormatConfig::load`].
+const CONFIG_VAR: &str = "WEF_CONFIG";
+
+/// Environment variable naming the preset to start from.
+const PRESET_VAR: &str = "WEF_PRESET";
+
+/// Key in a config file naming the preset to start from.
+const PRESET_KEY: &str = "preset";
+
+impl FormatConfig {
+    /// Names of the built-in presets accepted by [`FormatConfig::preset`].
+    pub const PRESETS: &'static [&'static str] = &["compact", "audit", "wide-table"];
+
+    /// Get a built-in preset by name.
+    ///
+    /// - `compact`: one line per window, titles truncated to 40 characters
+    /// - `audit`: CSV with headers and untruncated titles
+    /// - `wide-table`: table with untruncated titles
+    pub fn preset(name: &str) -> Result<Self, FormatError> {
+        let config = match name {
+            "compact" => Self {
+                format: OutputFormat::Simple,
+                show_headers: false,
+                max_title_length: Some(40),
+                ..Default::default()
+            },
+            "audit" => Self {
+                format: OutputFormat::Csv,
+                show_headers: true,
+                max_title_length: None,
+                ..Default::default()
+            },
+            "wide-table" => Self {
+                format: OutputFormat::Table,
+                show_headers: true,
+                max_title_length: None,
+                ..Default::default()
+            },
+            _ => {
+                return Err(FormatError::UnknownPreset {
+                    name: name.to_string(),
+                })
+            }
+        };
+        Ok(config)
+    }
+
+    /// Load a configuration file.
+    ///
+    /// The format is chosen from the extension: `.json`, `.yaml`/`.yml`, or
+    /// `.toml` (requires the `toml` feature). A top-level `preset` key selects
+    /// the preset the file's settings are applied on top of.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, FormatError> {
+        let file = read_config_file(path.as_ref())?;
+        Self::from_layers(file, std::iter::empty())
+    }
+
+    /// Build a configuration from `WEF_*` environment variables.
+    ///
+    /// Each variable maps to the field of the same name (`WEF_SHOW_HEADERS`
+    /// sets `show_headers`), with `__` separating nested fields. Values of
+    /// string settings are used as is (`WEF_HTML__PAGE_TITLE=2024`) unless they
+    /// are JSON objects (`WEF_TABLE__WIDTH={"fixed":120}`); other values are
+    /// parsed as JSON when possible (`false`, `30`, `null`) and used as plain
+    /// strings otherwise. Unset optional strings need JSON quotes around text
+    /// that looks like JSON (`WEF_REDACTION__HASH_SALT='"2024"'`).
+    /// `WEF_PRESET` selects a preset.
+    /// A `WEF_*` value that is not valid Unicode is an
+    /// [`InvalidConfig`](FormatError::InvalidConfig) error; other variables are ignored.
+    pub fn from_env() -> Result<Self, FormatError> {
+        Self::from_layers(Map::new(), env_vars()?)
+    }
+
+    /// Load a configuration from all sources, later layers overriding earlier ones:
+    ///
+    /// 1. [`FormatConfig::default`]
+    /// 2. the preset named by `WEF_PRESET`, or else by the file's `preset` key
+    /// 3. the config file at `path`, or else at `WEF_CONFIG` if set
+    /// 4. the remaining `WEF_*` environment variables
+    pub fn load(path: Option<&Path>) -> Result<Self, FormatError> {
+        let file = match path {
+            Some(path) => read_config_file(path)?,
+            None => match std::env::var_os(CONFIG_VAR) {
+                Some(path) => read_config_file(Path::new(&path))?,
+                None => Map::new(),
+            },
+        };
+        Self::from_layers(file, env_vars()?)
+    }
+
+    // Merge file and environment layers on top of the selected preset
+    fn from_layers(
+        mut file: Map<String, Value>,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, FormatError> {
+        let mut env_preset = None;
+        let mut env_values = Vec::new();
+        for (key, value) in vars {
+            let Some(name) = key.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            match key.as_str() {
+                PRESET_VAR => env_preset = Some(value),
+                CONFIG_VAR => {}
+                _ => env_values.push((name.to_string(), value)),
+            }
+        }
+
+        let file_preset = match file.remove(PRESET_KEY) {
+            Some(Value::String(name)) => Some(name),
+            Some(other) => {
+                return Err(FormatError::InvalidConfig {
+                    message: format!("`{}` must be a string, got {}", PRESET_KEY, other),
+                })
+            }
+            None => None,
+        };
+
+        let base = match env_preset.or(file_preset) {
+            Some(name) => Self::preset(&name)?,
+            None => Self::default(),
+        };
+
+        let mut merged = serde_json::to_value(base)?;
+        merge(&mut merged, Value::Object(file));
+        let mut env = Map::new();
+        for (name, value) in &env_values {
+            insert_env_value(&mut env, &merged, name, value);
+        }
+        merge(&mut merged, Value::Object(env));
+
+        let config: Self =
+            serde_json::from_value(merged).map_err(|e| FormatError::InvalidConfig {
+                message: e.to_string(),
+            })?;
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+// The `WEF_*` environment variables, skipping others that are not valid Unicode
+fn env_vars() -> Result<Vec<(String, String)>, FormatError> {
+    let mut vars = Vec::new();
+    for (key, value) in std::env::vars_os() {
+        let Some(key) = key.to_str().filter(|key| key.starts_with(ENV_PREFIX)) else {
+            continue;
+        };
+        let value = value
+            .into_string()
+            .map_err(|value| FormatError::InvalidConfig {
+                message: format!("{} is not valid Unicode: {:?}", key, value),
+            })?;
+        vars.push((key.to_string(), value));
+    }
+    Ok(vars)
+}
+
+// Read a config file into a JSON object, picking the parser by extension
+fn read_config_file(path: &Path) -> Result<Map<String, Value>, FormatError> {
+    let content = std::fs::read_to_string(path)?;
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    let value: Value = match extension.as_str() {
+        "json" => serde_json::from_str(&content)?,
+        "yaml" | "yml" => serde_yaml::from_str(&content)?,
+        #[cfg(feature = "toml")]
+        "toml" => toml::from_str(&content)?,
+        _ => {
+            return Err(FormatError::InvalidConfig {
+                message: format!("unsupported config file: {}", path.display()),
+            })
+        }
+    };
+
+    match value {
+        Value::Object(map) => Ok(map),
+        // An empty YAML document parses as null
+        Value::Null => Ok(Map::new()),
+        _ => Err(FormatError::InvalidConfig {
+            message: format!("{} must contain a table of settings", path.display()),
+        }),
+    }
+}
+
+// Insert `WEF_A__B=value` as `{"a": {"b": value}}`. Values are parsed as JSON,
+// except that a string in `current` (a text setting or a unit enum variant) is
+// only replaced by an object, such as `{"fixed":120}`, and otherwise by the raw text
+fn insert_env_value(target: &mut Map<String, Value>, current: &Value, name: &str, raw: &str) {
+    let path: Vec<String> = name.split("__").map(str::to_lowercase).collect();
+    let existing = path
+        .iter()
+        .try_fold(current, |value, key| value.as_object()?.get(key));
+    let value = match (existing, serde_json::from_str(raw)) {
+        (Some(Value::String(_)), Ok(Value::Object(object))) => Value::Object(object),
+        (Some(Value::String(_)), _) | (_, Err(_)) => Value::String(raw.to_string()),
+        (_, Ok(value)) => value,
+    };
+    let mut keys = path.into_iter().peekable();
+    let mut current = target;
+    while let Some(key) = keys.next() {
+        if keys.peek().is_none() {
+            current.insert(key, value);
+            return;
+        }
+        let entry = current
+            .entry(key)
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            *entry = Value::Object(Map::new());
+        }
+        current = entry
+            .as_object_mut()
+            .expect("entry was just made an object");
+    }
+}
+
+// Recursively merge objects; any other overlay value replaces the base value
+fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::TableWidth;
+
+    fn from_vars(vars: &[(&str, &str)]) -> Result<FormatConfig, FormatError> {
+        let vars = vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()));
+        FormatConfig::from_layers(Map::new(), vars)
+    }
+
+    #[test]
+    fn string_settings_keep_raw_values() {
+        let config = from_vars(&[
+            ("WEF_HTML__PAGE_TITLE", "2024"),
+            ("WEF_SQL__TABLE", "2024"),
+            ("WEF_CSV__NULL", "null"),
+        ])
+        .unwrap();
+        assert_eq!(config.html.page_title, "2024");
+        assert_eq!(config.sql.table, "2024");
+        assert_eq!(config.csv.null, "null");
+    }
+
+    #[test]
+    fn other_settings_parse_as_json() {
+        let config = from_vars(&[
+            ("WEF_SHOW_HEADERS", "false"),
+            ("WEF_MAX_TITLE_LENGTH", "null"),
+            ("WEF_TABLE__WIDTH", r#"{"fixed":120}"#),
+        ])
+        .unwrap();
+        assert!(!config.show_headers);
+        assert_eq!(config.max_title_length, None);
+        assert_eq!(config.table.width, TableWidth::Fixed(120));
+    }
+
+    #[cfg(feature = "redaction-hash")]
+    #[test]
+    fn unset_optional_strings_take_quoted_json() {
+        let config = from_vars(&[("WEF_REDACTION__HASH_SALT", r#""2024""#)]).unwrap();
+        assert_eq!(config.redaction.hash_salt.as_deref(), Some("2024"));
+    }
+}
@@ -0,0 +1,204 @@
+//! CSV and TSV dialects.
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// When to wrap CSV fields in quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuoteStyle {
+    /// Quote every field, including headers and numbers.
+    Always,
+    /// Quote fields containing the delimiter, the quote character, line breaks,
+    /// or leading/trailing spaces (RFC 4180).
+    #[default]
+    Necessary,
+    /// Never quote. Fields containing the delimiter or line breaks produce
+    /// output that cannot be parsed back unambiguously.
+    Never,
+}
+
+/// Line ending written after each CSV record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LineTerminator {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`, as required by RFC 4180 and expected by Excel.
+    Crlf,
+}
+
+impl LineTerminator {
+    fn as_str(self) -> &'static str {
+        match self {
+            LineTerminator::Lf => "\n",
+            LineTerminator::Crlf => "\r\n",
+        }
+    }
+}
+
+/// Dialect used by [`OutputFormat::Csv`](crate::OutputFormat::Csv) and
+/// [`OutputFormat::Tsv`](crate::OutputFormat::Tsv).
+///
+/// TSV output uses the same dialect with a tab delimiter.
+///
+/// ```
+/// use window_enumerator_formatter::{
+///     CsvDialect, FormatConfig, LineTerminator, OutputFormat, QuoteStyle, WindowInfo,
+///     WindowListFormat,
+/// };
+///
+/// let windows = vec![WindowInfo::builder()
+///     .index(1)
+///     .hwnd(10)
+///     .pid(20)
+///     .title(r#"Say "hi", all"#.to_string())
+///     .process_name("a\tb.exe".to_string())
+///     .build()];
+/// let format = |format: OutputFormat, csv: CsvDialect| {
+///     let config = FormatConfig::builder()
+///         .format(format)
+///         .show_headers(false)
+///         .csv(csv)
+///         .build()
+///         .unwrap();
+///     windows.format_output(&config)
+/// };
+///
+/// // Only fields with the delimiter, quotes or line breaks are quoted by default
+/// assert_eq!(
+///     format(OutputFormat::Csv, CsvDialect::default()),
+///     "1,10,20,\"Say \"\"hi\"\", all\",,a\tb.exe,,0,0,0,0\n"
+/// );
+/// // TSV uses the same rules with a tab delimiter
+/// assert_eq!(
+///     format(OutputFormat::Tsv, CsvDialect::default()),
+///     "1\t10\t20\t\"Say \"\"hi\"\", all\"\t\t\"a\tb.exe\"\t\t0\t0\t0\t0\n"
+/// );
+/// assert_eq!(
+///     format(
+///         OutputFormat::Csv,
+///         CsvDialect { quote_style: QuoteStyle::Always, ..Default::default() }
+///     ),
+///     "\"1\",\"10\",\"20\",\"Say \"\"hi\"\", all\",\"\",\"a\tb.exe\",\"\",\"0\",\"0\",\"0\",\"0\"\n"
+/// );
+/// assert_eq!(
+///     format(
+///         OutputFormat::Csv,
+///         CsvDialect {
+///             quote_style: QuoteStyle::Never,
+///             line_terminator: LineTerminator::Crlf,
+///             ..Default::default()
+///         }
+///     ),
+///     "1,10,20,Say \"hi\", all,,a\tb.exe,,0,0,0,0\r\n"
+/// );
+/// assert_eq!(
+///     format(
+///         OutputFormat::Csv,
+///         CsvDialect { delimiter: ';', bom: true, null: "null".to_string(), ..Default::default() }
+///     ),
+///     "\u{feff}1;10;20;\"Say \"\"hi\"\", all\";null;a\tb.exe;null;0;0;0;0\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CsvDialect {
+    /// Field delimiter.
+    pub delimiter: char,
+    /// Quote character; embedded quotes are escaped by doubling them.
+    pub quote: char,
+    /// When to quote fields.
+    pub quote_style: QuoteStyle,
+    /// Line ending after each record.
+    pub line_terminator: LineTerminator,
+    /// Whether to start the output with a UTF-8 byte order mark, which Excel
+    /// needs to detect UTF-8.
+    pub bom: bool,
+    /// Text written for empty fields.
+    pub null: String,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            quote: '"',
+            quote_style: QuoteStyle::Necessary,
+            line_terminator: LineTerminator::Lf,
+            bom: false,
+            null: String::new(),
+        }
+    }
+}
+
+/// Byte order mark written when [`CsvDialect::bom`] is set.
+pub(crate) const BOM: char = '\u{feff}';
+
+impl CsvDialect {
+    /// The same dialect with a tab delimiter.
+    pub fn tab_separated(&self) -> Self {
+        Self {
+            delimiter: '\t',
+            ..self.clone()
+        }
+    }
+
+    /// Write one record, escaping each field, followed by the line terminator.
+    pub(crate) fn write_record<S: AsRef<str>>(&self, output: &mut String, fields: &[S]) {
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                output.push(self.delimiter);
+            }
+            self.write_field(output, field.as_ref());
+        }
+        output.push_str(self.line_terminator.as_str());
+    }
+
+    fn write_field(&self, output: &mut String, field: &str) {
+        let field = if field.is_empty() { &self.null } else { field };
+        if !self.needs_quotes(field) {
+            output.push_str(field);
+            return;
+        }
+
+        output.push(self.quote);
+        for c in field.chars() {
+            if c == self.quote {
+                output.push(self.quote);
+            }
+            output.push(c);
+        }
+        output.push(self.quote);
+    }
+
+    fn needs_quotes(&self, field: &str) -> bool {
+        match self.quote_style {
+            QuoteStyle::Always => true,
+            QuoteStyle::Never => false,
+            QuoteStyle::Necessary => {
+                field.starts_with(' ')
+                    || field.ends_with(' ')
+                    || field
+                        .chars()
+                        .any(|c| c == self.delimiter || c == self.quote || c == '\r' || c == '\n')
+            }
+        }
+    }
+}
+
+/// Characters that make spreadsheet applications evaluate a cell as a formula.
+pub(crate) const FORMULA_TRIGGERS: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Prefix a text cell with `'` if a spreadsheet would evaluate it as a formula.
+///
+/// Only text columns (title, class, process, file) are passed through here, so
+/// negative coordinates keep their numeric value.
+pub(crate) fn neutralize_formula(field: &str) -> Cow<'_, str> {
+    if field.starts_with(FORMULA_TRIGGERS) {
+        Cow::Owned(format!("'{}", field))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
@@ -0,0 +1,63 @@
+//! Text encodings for the byte-oriented output API.
+
+use crate::csv::BOM;
+use serde::{Deserialize, Serialize};
+
+/// Encoding of text output written through
+/// [`WindowListFormat::format_bytes`](crate::WindowListFormat::format_bytes) and
+/// [`WindowListFormat::write_bytes`](crate::WindowListFormat::write_bytes).
+///
+/// The `String` API is always UTF-8 without a byte order mark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputEncoding {
+    /// UTF-8 without a byte order mark.
+    #[default]
+    Utf8,
+    /// UTF-8 with a byte order mark, which Excel needs to detect UTF-8.
+    Utf8Bom,
+    /// UTF-16 little-endian with a byte order mark, the default of Windows
+    /// PowerShell 5 redirection and Excel's "Unicode text".
+    Utf16Le,
+}
+
+impl OutputEncoding {
+    /// Encode text, writing a single byte order mark even when the text
+    /// already starts with one (see [`CsvDialect::bom`](crate::CsvDialect::bom)).
+    pub(crate) fn encode(self, text: String) -> Vec<u8> {
+        let body = text.strip_prefix(BOM).unwrap_or(&text);
+        match self {
+            OutputEncoding::Utf8 => text.into_bytes(),
+            OutputEncoding::Utf8Bom => {
+                let mut bytes = Vec::with_capacity(body.len() + 3);
+                bytes.extend_from_slice(BOM.encode_utf8(&mut [0; 3]).as_bytes());
+                bytes.extend_from_slice(body.as_bytes());
+                bytes
+            }
+            OutputEncoding::Utf16Le => std::iter::once(BOM)
+                .chain(body.chars())
+                .flat_map(|c| c.encode_utf16(&mut [0; 2]).to_vec())
+                .flat_map(u16::to_le_bytes)
+                .collect(),
+        }
+    }
+}
+
+/// Decode text written in any [`OutputEncoding`], detected by its byte order
+/// mark. The UTF-8 byte order mark is left for the parsers to skip.
+pub(crate) fn decode(bytes: &[u8]) -> Result<String, String> {
+    match bytes {
+        [0xff, 0xfe, rest @ ..] => {
+            if rest.len() % 2 != 0 {
+                return Err("UTF-16LE input has an odd number of bytes".to_string());
+            }
+            let units = rest
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]));
+            char::decode_utf16(units)
+                .collect::<Result<String, _>>()
+                .map_err(|error| error.to_string())
+        }
+        _ => String::from_utf8(bytes.to_vec()).map_err(|error| error.to_string()),
+    }
+}
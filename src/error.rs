@@ -1,7 +1,10 @@
 use thiserror::Error;
 
 /// Errors that can occur during formatting operations.
+///
+/// Some variants only exist with their feature enabled, so matches need a wildcard arm.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum FormatError {
     /// The input window list is empty.
     #[error("Cannot format empty window list")]
@@ -37,6 +40,88 @@ pub enum FormatError {
         source: serde_yaml::Error,
     },
 
+    /// TOML deserialization error.
+    #[cfg(feature = "toml")]
+    #[error("TOML error: {source}")]
+    TomlError {
+        /// The underlying error.
+        #[from]
+        source: toml::de::Error,
+    },
+
+    /// MessagePack decoding error.
+    #[cfg(feature = "messagepack")]
+    #[error("MessagePack error: {source}")]
+    MessagePackError {
+        /// The underlying error.
+        #[from]
+        source: rmp_serde::decode::Error,
+    },
+
+    /// CBOR decoding error.
+    #[cfg(feature = "cbor")]
+    #[error("CBOR error: {source}")]
+    CborError {
+        /// The underlying error.
+        #[from]
+        source: ciborium::de::Error<std::io::Error>,
+    },
+
+    /// SQLite export error.
+    #[cfg(feature = "rusqlite")]
+    #[error("SQLite error: {source}")]
+    SqliteError {
+        /// The underlying error.
+        #[from]
+        source: rusqlite::Error,
+    },
+
+    /// XML parsing error.
+    #[error("XML error: {message}")]
+    XmlError {
+        /// Error message.
+        message: String,
+    },
+
+    /// A process file path is not valid Unicode and
+    /// [`PathEncoding::Error`](crate::PathEncoding::Error) is set.
+    #[error("Process file path is not valid Unicode: {path}")]
+    InvalidPath {
+        /// The path, with invalid sequences replaced.
+        path: String,
+    },
+
+    /// I/O error while reading or writing a file.
+    #[error("I/O error: {source}")]
+    Io {
+        /// The underlying error.
+        #[from]
+        source: std::io::Error,
+    },
+
+    /// No built-in preset has the requested name.
+    #[error("Unknown preset: {name}")]
+    UnknownPreset {
+        /// The requested preset name.
+        name: String,
+    },
+
+    /// Configuration could not be loaded or is inconsistent.
+    #[error("Invalid configuration: {message}")]
+    InvalidConfig {
+        /// Error message.
+        message: String,
+    },
+
+    /// A snapshot was written with a newer schema than this version understands.
+    #[error("Unsupported snapshot schema version {found} (supported up to {supported})")]
+    UnsupportedSchema {
+        /// Schema version of the snapshot.
+        found: u32,
+        /// Newest schema version this version can read.
+        supported: u32,
+    },
+
     /// Other unspecified errors.
     #[error("Formatting error: {message}")]
     Other {
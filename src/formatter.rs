@@ -1,14 +1,59 @@
+#[cfg(any(feature = "messagepack", feature = "cbor"))]
+use crate::binary::hex;
+use crate::color::{named_style, ColorChoice, Painter, THEME};
+use crate::csv::{neutralize_formula, CsvDialect, BOM};
+use crate::encoding::OutputEncoding;
+use crate::error::FormatError;
+use crate::highlight::{highlight_json, highlight_yaml};
+use crate::html::HtmlOptions;
+use crate::layout::{FixedColumns, TableOptions, TableWidth};
+use crate::markdown::MarkdownOptions;
+use crate::minimap::{MinimapOptions, MIN_MINIMAP_SIZE};
 use crate::models::WindowInfo;
+use crate::path_encoding::PathEncoding;
+use crate::projection::{validate_fields, Items, Projection, Record};
+use crate::prometheus::PrometheusOptions;
+use crate::redact::{RedactionPolicy, REDACTED};
+use crate::sanitize::ControlChars;
+use crate::serialization::SerializationOptions;
+use crate::snapshot::{Envelope, SnapshotHeader};
+use crate::sql::{SqlOptions, COLUMNS};
+use crate::svg::SvgOptions;
+use crate::xml::XmlOptions;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::io::Write;
 
 /// Supported output formats.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Some variants only exist with their feature enabled, so matches need a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
 pub enum OutputFormat {
     /// Compact JSON format.
     Json,
     /// Pretty-printed JSON format.
     JsonPretty,
+    /// Newline-delimited JSON, one compact window record per line. An empty
+    /// list writes no lines.
+    ///
+    /// ```
+    /// use window_enumerator_formatter::{OutputFormat, Snapshot, WindowInfo, WindowListFormat};
+    ///
+    /// let windows: Vec<WindowInfo> = Vec::new();
+    /// let ndjson = windows.format_with(OutputFormat::Ndjson);
+    /// assert_eq!(ndjson, "");
+    /// assert!(Snapshot::parse(&ndjson).unwrap().windows.is_empty());
+    /// ```
+    Ndjson,
+    /// TOML array of `[[window]]` tables.
+    #[cfg(feature = "toml")]
+    Toml,
     /// CSV format.
     Csv,
+    /// Tab-separated values, using the CSV dialect with a tab delimiter.
+    Tsv,
     /// YAML format.
     Yaml,
     /// Simple one-line format.
@@ -17,32 +62,162 @@ pub enum OutputFormat {
     Detail,
     /// Formatted table.
     Table,
+    /// GitHub-flavored Markdown table.
+    Markdown,
+    /// Standalone HTML report.
+    Html,
+    /// SVG drawing of the window rectangles.
+    Svg,
+    /// Character-grid map of the window rectangles with a legend.
+    Minimap,
+    /// XML document with a `<window>` element per window.
+    Xml,
+    /// SQL `CREATE TABLE` and batched `INSERT` statements.
+    Sql,
+    /// Prometheus text exposition of per-process window gauges.
+    Prometheus,
+    /// MessagePack, with the same records and envelopes as JSON.
+    #[cfg(feature = "messagepack")]
+    MessagePack,
+    /// CBOR, with the same records and envelopes as JSON.
+    #[cfg(feature = "cbor")]
+    Cbor,
     /// Custom template format.
     Custom,
 }
 
+impl OutputFormat {
+    /// Whether the format is meant to be read in a terminal, as opposed to
+    /// being parsed by a program or rendered by a browser.
+    pub fn is_human_readable(self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Simple
+                | OutputFormat::Detail
+                | OutputFormat::Table
+                | OutputFormat::Markdown
+                | OutputFormat::Minimap
+                | OutputFormat::Custom
+        )
+    }
+
+    /// Whether the format serializes window records, as opposed to rendering
+    /// window fields as text.
+    pub(crate) fn writes_records(self) -> bool {
+        match self {
+            OutputFormat::Json
+            | OutputFormat::JsonPretty
+            | OutputFormat::Ndjson
+            | OutputFormat::Yaml => true,
+            #[cfg(feature = "toml")]
+            OutputFormat::Toml => true,
+            _ => self.is_binary(),
+        }
+    }
+
+    /// Whether the format produces bytes rather than text. The `String` API
+    /// returns such output hex-encoded; use
+    /// [`WindowFormatter::format_windows_bytes`] for the raw bytes.
+    pub fn is_binary(self) -> bool {
+        match self {
+            #[cfg(feature = "messagepack")]
+            OutputFormat::MessagePack => true,
+            #[cfg(feature = "cbor")]
+            OutputFormat::Cbor => true,
+            _ => false,
+        }
+    }
+}
+
 /// Template formats for custom output.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TemplateFormat {
     /// Output only the values of specified fields (tab-separated).
+    ///
+    /// With JSON and YAML formats, selects the serialized fields instead. These
+    /// also accept dotted paths (`position.x`), `position` as a whole, and the
+    /// computed fields `area`, `right` and `bottom`.
     Fields(Vec<String>),
-    /// Output field names and values.
+    /// Output field names and values. Selects fields like
+    /// [`TemplateFormat::Fields`] with JSON and YAML formats.
     KeyValue(Vec<String>),
     /// Custom template string with placeholders.
     Custom(String),
 }
 
 /// Configuration for formatting output.
-#[derive(Debug, Clone)]
+///
+/// Missing fields fall back to [`FormatConfig::default`] when deserializing, so
+/// config files only need to list the settings they change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct FormatConfig {
     /// The output format to use.
     pub format: OutputFormat,
-    /// Template configuration for custom formats.
+    /// Template configuration for custom formats, or the fields included in
+    /// JSON and YAML output.
     pub template: Option<TemplateFormat>,
     /// Whether to show headers in CSV/Table formats.
     pub show_headers: bool,
     /// Maximum title length before truncation.
     pub max_title_length: Option<usize>,
+    /// Maximum number of windows to output from a list.
+    pub limit: Option<usize>,
+    /// Number of windows to skip at the start of a list.
+    pub offset: usize,
+    /// Rows per page in Table output. Each page repeats the header and ends
+    /// with a "showing 1–50 of 312" footer.
+    pub page_size: Option<usize>,
+    /// Wrap JSON and YAML lists as `{ total, offset, items }`.
+    pub page_envelope: bool,
+    /// Wrap JSON and YAML lists in a versioned snapshot envelope, and start
+    /// NDJSON output with a header line. See [`Snapshot`](crate::Snapshot).
+    /// Empty lists are written as envelopes with no windows rather than
+    /// "No windows found", as with `page_envelope`.
+    pub snapshot: bool,
+    /// Write `x`, `y`, `width` and `height` as top-level JSON and YAML keys
+    /// instead of nesting them under `position`.
+    pub flatten_position: bool,
+    /// Key case, handle encoding and path style of JSON and YAML output.
+    pub serialization: SerializationOptions,
+    /// Layout options for Table output.
+    pub table: TableOptions,
+    /// Dialect for CSV and TSV output.
+    pub csv: CsvDialect,
+    /// Prefix text cells starting with `=`, `+`, `-`, `@`, tab or carriage return
+    /// with `'` in CSV, TSV and Markdown output, so spreadsheets opening the file
+    /// do not evaluate window titles as formulas.
+    pub escape_formulas: bool,
+    /// When to color Table, Simple and Detail output and template styles.
+    pub color: ColorChoice,
+    /// Syntax-highlight JSON and YAML output when `color` allows it. With
+    /// [`ColorChoice::Auto`] output is only highlighted when stdout is a terminal.
+    /// The byte API never highlights.
+    pub highlight: bool,
+    /// How control characters and ANSI escapes in window strings are shown by
+    /// human-readable formats.
+    pub control_chars: ControlChars,
+    /// Fields to mask, anonymize or drop in every format.
+    pub redaction: RedactionPolicy,
+    /// Options for Markdown output.
+    pub markdown: MarkdownOptions,
+    /// Options for HTML output.
+    pub html: HtmlOptions,
+    /// Options for SVG output and the HTML preview.
+    pub svg: SvgOptions,
+    /// Options for minimap output.
+    pub minimap: MinimapOptions,
+    /// Options for XML output.
+    pub xml: XmlOptions,
+    /// Options for SQL output and SQLite export.
+    pub sql: SqlOptions,
+    /// Options for Prometheus output.
+    pub prometheus: PrometheusOptions,
+    /// Encoding of text output written through the byte API.
+    pub encoding: OutputEncoding,
+    /// How process file paths that are not valid Unicode are written.
+    pub path_encoding: PathEncoding,
 }
 
 impl Default for FormatConfig {
@@ -52,167 +227,997 @@ impl Default for FormatConfig {
             template: None,
             show_headers: true,
             max_title_length: Some(50),
+            limit: None,
+            offset: 0,
+            page_size: None,
+            page_envelope: false,
+            snapshot: false,
+            flatten_position: false,
+            serialization: SerializationOptions::default(),
+            table: TableOptions::default(),
+            csv: CsvDialect::default(),
+            escape_formulas: true,
+            color: ColorChoice::default(),
+            highlight: false,
+            control_chars: ControlChars::default(),
+            redaction: RedactionPolicy::default(),
+            markdown: MarkdownOptions::default(),
+            html: HtmlOptions::default(),
+            svg: SvgOptions::default(),
+            minimap: MinimapOptions::default(),
+            xml: XmlOptions::default(),
+            sql: SqlOptions::default(),
+            prometheus: PrometheusOptions::default(),
+            encoding: OutputEncoding::Utf8,
+            path_encoding: PathEncoding::Lossy,
+        }
+    }
+}
+
+/// Shortest `max_title_length` that leaves room for the `...` ellipsis.
+pub const MIN_TITLE_LENGTH: usize = 4;
+
+/// Field names accepted by templates (case-insensitive).
+pub const TEMPLATE_FIELDS: &[&str] = &[
+    "index", "hwnd", "pid", "title", "class", "process", "file", "x", "y", "width", "height",
+];
+
+impl FormatConfig {
+    /// Create a new FormatConfig using builder pattern.
+    pub fn builder() -> FormatConfigBuilder {
+        FormatConfigBuilder::default()
+    }
+
+    /// Check that the settings are consistent with each other.
+    pub fn validate(&self) -> Result<(), FormatError> {
+        match (self.format, &self.template) {
+            (OutputFormat::Custom, None) => {
+                return Err(FormatError::InvalidConfig {
+                    message: "OutputFormat::Custom requires a template".to_string(),
+                })
+            }
+            (OutputFormat::Custom | OutputFormat::Simple, Some(template)) => {
+                Self::validate_template(template)?
+            }
+            (
+                OutputFormat::Json
+                | OutputFormat::JsonPretty
+                | OutputFormat::Ndjson
+                | OutputFormat::Yaml,
+                Some(TemplateFormat::Fields(fields) | TemplateFormat::KeyValue(fields)),
+            ) => validate_fields(fields)?,
+            #[cfg(feature = "toml")]
+            (
+                OutputFormat::Toml,
+                Some(TemplateFormat::Fields(fields) | TemplateFormat::KeyValue(fields)),
+            ) => validate_fields(fields)?,
+            #[cfg(feature = "messagepack")]
+            (
+                OutputFormat::MessagePack,
+                Some(TemplateFormat::Fields(fields) | TemplateFormat::KeyValue(fields)),
+            ) => validate_fields(fields)?,
+            #[cfg(feature = "cbor")]
+            (
+                OutputFormat::Cbor,
+                Some(TemplateFormat::Fields(fields) | TemplateFormat::KeyValue(fields)),
+            ) => validate_fields(fields)?,
+            (format, Some(_)) => {
+                return Err(FormatError::InvalidConfig {
+                    message: format!(
+                        "template is only used by Custom and Simple formats, and field lists by JSON and YAML, not {:?}",
+                        format
+                    ),
+                })
+            }
+            (_, None) => {}
+        }
+
+        if let Some(max) = self.max_title_length {
+            if max < MIN_TITLE_LENGTH {
+                return Err(FormatError::InvalidConfig {
+                    message: format!(
+                        "max_title_length must be at least {}, got {}",
+                        MIN_TITLE_LENGTH, max
+                    ),
+                });
+            }
         }
+
+        for (name, value) in [("limit", self.limit), ("page_size", self.page_size)] {
+            if value == Some(0) {
+                return Err(FormatError::InvalidConfig {
+                    message: format!("{} must be at least 1", name),
+                });
+            }
+        }
+
+        if self.snapshot && self.page_envelope {
+            return Err(FormatError::InvalidConfig {
+                message: "snapshot and page_envelope cannot both be set".to_string(),
+            });
+        }
+
+        if self.format.is_binary() && self.encoding != OutputEncoding::Utf8 {
+            return Err(FormatError::InvalidConfig {
+                message: format!("{:?} output has no text encoding", self.format),
+            });
+        }
+
+        self.redaction.validate()?;
+
+        if !self.prometheus.is_valid() {
+            return Err(FormatError::InvalidConfig {
+                message: format!(
+                    "prometheus prefix `{}` is not a valid metric name",
+                    self.prometheus.prefix
+                ),
+            });
+        }
+
+        if self.sql.table.is_empty() || self.sql.batch_size == 0 {
+            return Err(FormatError::InvalidConfig {
+                message: "sql table must be named and batch_size must be at least 1".to_string(),
+            });
+        }
+
+        if self.format == OutputFormat::Sql
+            && COLUMNS
+                .iter()
+                .all(|column| self.redaction.drops(column.field))
+        {
+            return Err(FormatError::InvalidConfig {
+                message: "redaction drops every SQL column".to_string(),
+            });
+        }
+
+        let csv = &self.csv;
+        if csv.delimiter == csv.quote
+            || [csv.delimiter, csv.quote]
+                .iter()
+                .any(|c| "\r\n".contains(*c))
+        {
+            return Err(FormatError::InvalidConfig {
+                message: format!(
+                    "CSV delimiter {:?} and quote {:?} must differ and not be line breaks",
+                    csv.delimiter, csv.quote
+                ),
+            });
+        }
+
+        if self.minimap.width < MIN_MINIMAP_SIZE || self.minimap.height < MIN_MINIMAP_SIZE {
+            return Err(FormatError::InvalidConfig {
+                message: format!(
+                    "minimap must be at least {}x{} characters, got {}x{}",
+                    MIN_MINIMAP_SIZE, MIN_MINIMAP_SIZE, self.minimap.width, self.minimap.height
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Check that a template only references known fields
+    fn validate_template(template: &TemplateFormat) -> Result<(), FormatError> {
+        let fields: Vec<&str> = match template {
+            TemplateFormat::Fields(fields) | TemplateFormat::KeyValue(fields) => {
+                if fields.is_empty() {
+                    return Err(FormatError::TemplateError {
+                        message: "template field list is empty".to_string(),
+                    });
+                }
+                fields.iter().map(String::as_str).collect()
+            }
+            TemplateFormat::Custom(template) => {
+                let mut names = Vec::new();
+                for placeholder in placeholders(template) {
+                    let mut parts = placeholder.split('|');
+                    names.extend(parts.next());
+                    if let Some(filter) = parts.find(|filter| template_style(filter).is_none()) {
+                        return Err(FormatError::TemplateError {
+                            message: format!("unknown style `{}` in {{{}}}", filter, placeholder),
+                        });
+                    }
+                }
+                names
+            }
+        };
+
+        match fields
+            .into_iter()
+            .find(|field| !TEMPLATE_FIELDS.contains(&field.to_lowercase().as_str()))
+        {
+            Some(field) => Err(FormatError::InvalidField {
+                field: field.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+// Placeholders between braces in a custom template, e.g. `title|color:red` in `{title|color:red}`
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template.split('{').skip(1).filter_map(|rest| {
+        let (placeholder, _) = rest.split_once('}')?;
+        let name = placeholder.split('|').next().unwrap_or_default();
+        let is_name = !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        is_name.then_some(placeholder)
+    })
+}
+
+// SGR parameters for a template style such as `color:red` or `bold`
+fn template_style(filter: &str) -> Option<&'static str> {
+    named_style(filter.strip_prefix("color:").unwrap_or(filter))
+}
+
+/// Builder for FormatConfig that validates the settings on build.
+#[derive(Default)]
+pub struct FormatConfigBuilder {
+    config: FormatConfig,
+}
+
+impl FormatConfigBuilder {
+    /// Set the output format.
+    pub fn format(mut self, format: OutputFormat) -> Self {
+        self.config.format = format;
+        self
+    }
+
+    /// Set the template for custom formats.
+    pub fn template(mut self, template: TemplateFormat) -> Self {
+        self.config.template = Some(template);
+        self
+    }
+
+    /// Set whether to show headers in CSV/Table formats.
+    pub fn show_headers(mut self, show_headers: bool) -> Self {
+        self.config.show_headers = show_headers;
+        self
+    }
+
+    /// Set the maximum title length, or `None` to disable truncation.
+    pub fn max_title_length(mut self, max_title_length: Option<usize>) -> Self {
+        self.config.max_title_length = max_title_length;
+        self
+    }
+
+    /// Set the maximum number of windows to output from a list.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.config.limit = Some(limit);
+        self
+    }
+
+    /// Set the number of windows to skip at the start of a list.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.config.offset = offset;
+        self
+    }
+
+    /// Set the number of rows per page in Table output.
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        self.config.page_size = Some(page_size);
+        self
+    }
+
+    /// Set whether to wrap JSON and YAML lists as `{ total, offset, items }`.
+    pub fn page_envelope(mut self, page_envelope: bool) -> Self {
+        self.config.page_envelope = page_envelope;
+        self
+    }
+
+    /// Set whether to wrap lists in a versioned snapshot envelope.
+    pub fn snapshot(mut self, snapshot: bool) -> Self {
+        self.config.snapshot = snapshot;
+        self
+    }
+
+    /// Set whether to write position fields as top-level JSON and YAML keys.
+    pub fn flatten_position(mut self, flatten_position: bool) -> Self {
+        self.config.flatten_position = flatten_position;
+        self
+    }
+
+    /// Set the key case, handle encoding and path style of JSON and YAML output.
+    pub fn serialization(mut self, serialization: SerializationOptions) -> Self {
+        self.config.serialization = serialization;
+        self
+    }
+
+    /// Set the layout options for Table output.
+    pub fn table(mut self, table: TableOptions) -> Self {
+        self.config.table = table;
+        self
+    }
+
+    /// Set the dialect for CSV and TSV output.
+    pub fn csv(mut self, csv: CsvDialect) -> Self {
+        self.config.csv = csv;
+        self
+    }
+
+    /// Set whether to neutralize spreadsheet formulas in CSV, TSV and Markdown.
+    pub fn escape_formulas(mut self, escape_formulas: bool) -> Self {
+        self.config.escape_formulas = escape_formulas;
+        self
+    }
+
+    /// Set when to color human-readable output.
+    pub fn color(mut self, color: ColorChoice) -> Self {
+        self.config.color = color;
+        self
+    }
+
+    /// Set whether to syntax-highlight JSON and YAML output.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.config.highlight = highlight;
+        self
+    }
+
+    /// Set how control characters are shown by human-readable formats.
+    pub fn control_chars(mut self, control_chars: ControlChars) -> Self {
+        self.config.control_chars = control_chars;
+        self
+    }
+
+    /// Set the redaction policy applied to every format.
+    pub fn redaction(mut self, redaction: RedactionPolicy) -> Self {
+        self.config.redaction = redaction;
+        self
+    }
+
+    /// Set the options for Markdown output.
+    pub fn markdown(mut self, markdown: MarkdownOptions) -> Self {
+        self.config.markdown = markdown;
+        self
+    }
+
+    /// Set the options for HTML output.
+    pub fn html(mut self, html: HtmlOptions) -> Self {
+        self.config.html = html;
+        self
+    }
+
+    /// Set the options for SVG output.
+    pub fn svg(mut self, svg: SvgOptions) -> Self {
+        self.config.svg = svg;
+        self
+    }
+
+    /// Set the options for minimap output.
+    pub fn minimap(mut self, minimap: MinimapOptions) -> Self {
+        self.config.minimap = minimap;
+        self
+    }
+
+    /// Set the options for XML output.
+    pub fn xml(mut self, xml: XmlOptions) -> Self {
+        self.config.xml = xml;
+        self
+    }
+
+    /// Set the options for SQL output and SQLite export.
+    pub fn sql(mut self, sql: SqlOptions) -> Self {
+        self.config.sql = sql;
+        self
+    }
+
+    /// Set the options for Prometheus output.
+    pub fn prometheus(mut self, prometheus: PrometheusOptions) -> Self {
+        self.config.prometheus = prometheus;
+        self
+    }
+
+    /// Set the encoding of text output written through the byte API.
+    pub fn encoding(mut self, encoding: OutputEncoding) -> Self {
+        self.config.encoding = encoding;
+        self
+    }
+
+    /// Set how process file paths that are not valid Unicode are written.
+    pub fn path_encoding(mut self, path_encoding: PathEncoding) -> Self {
+        self.config.path_encoding = path_encoding;
+        self
+    }
+
+    /// Build the FormatConfig, rejecting contradictory settings.
+    pub fn build(self) -> Result<FormatConfig, FormatError> {
+        self.config.validate()?;
+        Ok(self.config)
     }
 }
 
 /// Main formatter for window information.
 pub struct WindowFormatter;
 
+// Apply a transformation to a possibly borrowed window, borrowing as long as nothing changes
+fn chain<'a>(
+    window: Cow<'a, WindowInfo>,
+    transform: impl FnOnce(&WindowInfo) -> Cow<'_, WindowInfo>,
+) -> Cow<'a, WindowInfo> {
+    match window {
+        Cow::Borrowed(window) => transform(window),
+        Cow::Owned(window) => Cow::Owned(transform(&window).into_owned()),
+    }
+}
+
+/// Output of a format, before choosing between the `String` and byte APIs.
+enum Rendered {
+    Text(String),
+    #[cfg(any(feature = "messagepack", feature = "cbor"))]
+    Bytes(Vec<u8>),
+}
+
+impl Rendered {
+    fn into_string(self) -> String {
+        match self {
+            Rendered::Text(text) => text,
+            #[cfg(any(feature = "messagepack", feature = "cbor"))]
+            Rendered::Bytes(bytes) => hex(&bytes),
+        }
+    }
+
+    fn into_bytes(self, encoding: OutputEncoding) -> Vec<u8> {
+        match self {
+            Rendered::Text(text) => encoding.encode(text),
+            #[cfg(any(feature = "messagepack", feature = "cbor"))]
+            Rendered::Bytes(bytes) => bytes,
+        }
+    }
+}
+
 impl WindowFormatter {
     /// Format a single window according to the configuration.
     pub fn format_window(window: &WindowInfo, config: &FormatConfig) -> String {
-        match config.format {
-            OutputFormat::Json => {
-                serde_json::to_string(window).unwrap_or_else(|_| "{}".to_string())
-            }
-            OutputFormat::JsonPretty => {
-                serde_json::to_string_pretty(window).unwrap_or_else(|_| "{}".to_string())
-            }
-            OutputFormat::Yaml => {
-                serde_yaml::to_string(window).unwrap_or_else(|_| "---".to_string())
-            }
+        Self::render_window(window, config).into_string()
+    }
+
+    /// Format a single window, after checking its path with
+    /// [`check_paths`](Self::check_paths).
+    pub fn try_format_window(
+        window: &WindowInfo,
+        config: &FormatConfig,
+    ) -> Result<String, FormatError> {
+        Self::check_paths(std::slice::from_ref(window), config)?;
+        Ok(Self::format_window(window, config))
+    }
+
+    /// Format a single window as bytes: MessagePack and CBOR as encoded, and
+    /// text formats in [`FormatConfig::encoding`].
+    /// JSON and YAML are not highlighted, and [`ColorChoice::Auto`] does not color.
+    pub fn format_window_bytes(window: &WindowInfo, config: &FormatConfig) -> Vec<u8> {
+        Self::render_window(window, &Self::byte_config(config)).into_bytes(config.encoding)
+    }
+
+    // Byte output goes to files and pipes rather than stdout: JSON and YAML are
+    // never highlighted and `Auto` does not color
+    fn byte_config(config: &FormatConfig) -> Cow<'_, FormatConfig> {
+        if !config.highlight && config.color != ColorChoice::Auto {
+            return Cow::Borrowed(config);
+        }
+        Cow::Owned(FormatConfig {
+            highlight: false,
+            color: match config.color {
+                ColorChoice::Auto => ColorChoice::Never,
+                color => color,
+            },
+            ..config.clone()
+        })
+    }
+
+    fn render_window(window: &WindowInfo, config: &FormatConfig) -> Rendered {
+        let window = &*Self::display_window(window, config);
+        let projection = Projection::new(config);
+        let record = projection.record(window);
+        let text = match config.format {
+            OutputFormat::Json | OutputFormat::Ndjson => Self::highlight(
+                serde_json::to_string(&record).unwrap_or_else(|_| "{}".to_string()),
+                config,
+            ),
+            OutputFormat::JsonPretty => Self::highlight(
+                serde_json::to_string_pretty(&record).unwrap_or_else(|_| "{}".to_string()),
+                config,
+            ),
+            OutputFormat::Yaml => Self::highlight(
+                serde_yaml::to_string(&record).unwrap_or_else(|_| "---".to_string()),
+                config,
+            ),
+            #[cfg(feature = "toml")]
+            OutputFormat::Toml => Self::format_toml(std::slice::from_ref(&record), config),
             OutputFormat::Simple => Self::format_simple(window, config),
             OutputFormat::Detail => Self::format_detail(window, config),
-            OutputFormat::Table => Self::format_table_single(window),
+            OutputFormat::Table => Self::format_table_single(window, config),
+            OutputFormat::Markdown => Self::format_markdown(std::slice::from_ref(window), config),
+            OutputFormat::Html => Self::format_html(std::slice::from_ref(window), config),
+            OutputFormat::Svg => Self::format_svg(std::slice::from_ref(window), config),
+            OutputFormat::Minimap => Self::format_minimap(std::slice::from_ref(window), config),
+            OutputFormat::Xml => Self::format_xml(std::slice::from_ref(window), config),
+            OutputFormat::Sql => Self::format_sql(std::slice::from_ref(window), config),
+            OutputFormat::Prometheus => {
+                Self::format_prometheus(std::slice::from_ref(window), config)
+            }
             OutputFormat::Custom => Self::format_custom(window, config),
-            OutputFormat::Csv => Self::format_csv_single(window),
-        }
+            OutputFormat::Csv => Self::format_csv_single(window, config, &config.csv),
+            OutputFormat::Tsv => {
+                Self::format_csv_single(window, config, &config.csv.tab_separated())
+            }
+            #[cfg(feature = "messagepack")]
+            OutputFormat::MessagePack => {
+                return Rendered::Bytes(Self::encode_binary(&record, config.format))
+            }
+            #[cfg(feature = "cbor")]
+            OutputFormat::Cbor => {
+                return Rendered::Bytes(Self::encode_binary(&record, config.format))
+            }
+        };
+        Rendered::Text(text)
     }
 
     /// Format a list of windows according to the configuration.
     pub fn format_windows(windows: &[WindowInfo], config: &FormatConfig) -> String {
-        if windows.is_empty() {
-            return "No windows found".to_string();
+        Self::render_windows(windows, config).into_string()
+    }
+
+    /// Format a list of windows, after checking their paths with
+    /// [`check_paths`](Self::check_paths).
+    pub fn try_format_windows(
+        windows: &[WindowInfo],
+        config: &FormatConfig,
+    ) -> Result<String, FormatError> {
+        Self::check_paths(windows, config)?;
+        Ok(Self::format_windows(windows, config))
+    }
+
+    /// Format a list of windows as bytes: MessagePack and CBOR as encoded, and
+    /// text formats in [`FormatConfig::encoding`].
+    /// JSON and YAML are not highlighted, and [`ColorChoice::Auto`] does not color.
+    ///
+    /// ```
+    /// # #[cfg(feature = "messagepack")]
+    /// # {
+    /// use window_enumerator_formatter::{
+    ///     FormatConfig, OutputFormat, Snapshot, WindowFormatter, WindowInfo,
+    /// };
+    ///
+    /// let windows = vec![WindowInfo::builder().hwnd(0x10318).title("Editor".to_string()).build()];
+    /// let config = FormatConfig::builder()
+    ///     .format(OutputFormat::MessagePack)
+    ///     .snapshot(true)
+    ///     .build()
+    ///     .unwrap();
+    /// let bytes = WindowFormatter::format_windows_bytes(&windows, &config);
+    ///
+    /// let snapshot = Snapshot::parse_bytes(&bytes, OutputFormat::MessagePack).unwrap();
+    /// assert_eq!(snapshot.windows[0].hwnd, 0x10318);
+    /// assert_eq!(snapshot.windows[0].title, "Editor");
+    /// # }
+    /// ```
+    pub fn format_windows_bytes(windows: &[WindowInfo], config: &FormatConfig) -> Vec<u8> {
+        Self::render_windows(windows, &Self::byte_config(config)).into_bytes(config.encoding)
+    }
+
+    /// Write a list of windows as bytes to `writer`, after checking their paths
+    /// with [`check_paths`](Self::check_paths).
+    pub fn write_windows_bytes(
+        windows: &[WindowInfo],
+        config: &FormatConfig,
+        writer: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Self::check_paths(windows, config)?;
+        writer.write_all(&Self::format_windows_bytes(windows, config))?;
+        Ok(())
+    }
+
+    /// Check that no process file path is refused by [`PathEncoding::Error`].
+    ///
+    /// The infallible `String` and byte APIs write refused paths lossily; use
+    /// the `try_format` and `write_bytes` methods, or call this first, to reject
+    /// them instead.
+    pub fn check_paths(windows: &[WindowInfo], config: &FormatConfig) -> Result<(), FormatError> {
+        if config.path_encoding != PathEncoding::Error || config.redaction.drops("file") {
+            return Ok(());
+        }
+        match windows.iter().find(|w| w.process_file.to_str().is_none()) {
+            Some(window) => Err(FormatError::InvalidPath {
+                path: window.process_file.to_string_lossy().into_owned(),
+            }),
+            None => Ok(()),
         }
+    }
 
-        match config.format {
-            OutputFormat::Json => {
-                serde_json::to_string(windows).unwrap_or_else(|_| "[]".to_string())
-            }
-            OutputFormat::JsonPretty => {
-                serde_json::to_string_pretty(windows).unwrap_or_else(|_| "[]".to_string())
-            }
-            OutputFormat::Yaml => {
-                serde_yaml::to_string(windows).unwrap_or_else(|_| "---".to_string())
+    fn render_windows(windows: &[WindowInfo], config: &FormatConfig) -> Rendered {
+        let total = windows.len();
+        let offset = config.offset.min(total);
+        let windows = &windows[offset..];
+        let windows = &windows[..config.limit.unwrap_or(total).min(windows.len())];
+
+        // Binary formats, NDJSON, metrics, SQL, XML and envelopes still describe an empty list
+        let envelope = (config.snapshot || config.page_envelope) && config.format.writes_records();
+        if windows.is_empty()
+            && !envelope
+            && !config.format.is_binary()
+            && !matches!(
+                config.format,
+                OutputFormat::Ndjson
+                    | OutputFormat::Prometheus
+                    | OutputFormat::Sql
+                    | OutputFormat::Xml
+            )
+        {
+            return Rendered::Text("No windows found".to_string());
+        }
+
+        let displayed: Vec<WindowInfo>;
+        let windows = if config.format.is_human_readable()
+            || config.redaction.is_active()
+            || windows.iter().any(|w| w.process_file.to_str().is_none())
+        {
+            displayed = windows
+                .iter()
+                .map(|w| Self::display_window(w, config).into_owned())
+                .collect();
+            &displayed
+        } else {
+            windows
+        };
+
+        let projection = Projection::new(config);
+        let records: Vec<Record> = windows.iter().map(|w| projection.record(w)).collect();
+        let items = if config.snapshot {
+            Items::Snapshot(Envelope {
+                header: Self::snapshot_header(records.len(), config),
+                windows: &records,
+            })
+        } else if config.page_envelope {
+            Items::Page {
+                total,
+                offset,
+                items: &records,
+                key_case: config.serialization.key_case,
             }
+        } else {
+            Items::Bare(&records)
+        };
+
+        let text = match config.format {
+            OutputFormat::Json => Self::highlight(
+                serde_json::to_string(&items).unwrap_or_else(|_| "[]".to_string()),
+                config,
+            ),
+            OutputFormat::JsonPretty => Self::highlight(
+                serde_json::to_string_pretty(&items).unwrap_or_else(|_| "[]".to_string()),
+                config,
+            ),
+            OutputFormat::Ndjson => Self::highlight(Self::format_ndjson(&records, config), config),
+            #[cfg(feature = "toml")]
+            OutputFormat::Toml => Self::format_toml(&records, config),
+            OutputFormat::Yaml => Self::highlight(
+                serde_yaml::to_string(&items).unwrap_or_else(|_| "---".to_string()),
+                config,
+            ),
             OutputFormat::Simple => Self::format_simple_list(windows, config),
             OutputFormat::Detail => Self::format_detail_list(windows, config),
-            OutputFormat::Table => Self::format_table(windows, config),
+            OutputFormat::Table => Self::format_table_pages(windows, total, offset, config),
+            OutputFormat::Markdown => Self::format_markdown(windows, config),
+            OutputFormat::Html => Self::format_html(windows, config),
+            OutputFormat::Svg => Self::format_svg(windows, config),
+            OutputFormat::Minimap => Self::format_minimap(windows, config),
+            OutputFormat::Xml => Self::format_xml(windows, config),
+            OutputFormat::Sql => Self::format_sql(windows, config),
+            OutputFormat::Prometheus => Self::format_prometheus(windows, config),
             OutputFormat::Custom => Self::format_custom_list(windows, config),
-            OutputFormat::Csv => Self::format_csv(windows, config),
+            OutputFormat::Csv => Self::format_csv(windows, config, &config.csv),
+            OutputFormat::Tsv => Self::format_csv(windows, config, &config.csv.tab_separated()),
+            #[cfg(feature = "messagepack")]
+            OutputFormat::MessagePack => {
+                return Rendered::Bytes(Self::encode_binary(&items, config.format))
+            }
+            #[cfg(feature = "cbor")]
+            OutputFormat::Cbor => {
+                return Rendered::Bytes(Self::encode_binary(&items, config.format))
+            }
+        };
+        Rendered::Text(text)
+    }
+
+    // NDJSON format - one record per line, after a header line for snapshots
+    fn format_ndjson(records: &[Record], config: &FormatConfig) -> String {
+        let mut lines = Vec::with_capacity(records.len() + 1);
+        if config.snapshot {
+            let header = Self::snapshot_header(records.len(), config);
+            lines.push(serde_json::to_string(&header).unwrap_or_else(|_| "{}".to_string()));
+        }
+        lines.extend(
+            records
+                .iter()
+                .map(|record| serde_json::to_string(record).unwrap_or_else(|_| "{}".to_string())),
+        );
+        lines.join("\n")
+    }
+
+    // Snapshot metadata; the host name is left out of redacted output
+    pub(crate) fn snapshot_header(count: usize, config: &FormatConfig) -> SnapshotHeader {
+        SnapshotHeader::now(
+            count,
+            config.serialization.key_case,
+            config.redaction.is_active(),
+        )
+    }
+
+    // Syntax-highlight structured output if enabled
+    fn highlight(output: String, config: &FormatConfig) -> String {
+        let painter = Painter::new(config.color);
+        if !config.highlight || !painter.is_enabled() {
+            return output;
+        }
+        match config.format {
+            OutputFormat::Json | OutputFormat::JsonPretty | OutputFormat::Ndjson => {
+                highlight_json(&output, painter)
+            }
+            OutputFormat::Yaml => highlight_yaml(&output, painter),
+            _ => output,
         }
     }
 
+    // Window with the path encoded for text formats and control characters
+    // handled for human-readable formats
+    fn display_window<'a>(window: &'a WindowInfo, config: &FormatConfig) -> Cow<'a, WindowInfo> {
+        let mut window = window.redacted(&config.redaction);
+        if !config.format.writes_records() {
+            window = chain(window, |w| w.with_path_encoding(config.path_encoding));
+        }
+        if config.format.is_human_readable() {
+            window = chain(window, |w| w.sanitized(config.control_chars));
+        }
+        window
+    }
+
     // Simple format - single window
     fn format_simple(window: &WindowInfo, config: &FormatConfig) -> String {
         if let Some(template) = &config.template {
-            return Self::apply_template(window, template);
+            return Self::apply_template(window, template, config);
+        }
+
+        let painter = Painter::new(config.color);
+        let policy = &config.redaction;
+        let title = Self::display_title(window, config);
+        let pid = policy.show("pid", window.pid);
+        if Self::is_zero_size(window, config) {
+            let line = format!(
+                "[{}] {} (PID: {}) @ ({},{})",
+                policy.show("index", window.index),
+                title,
+                pid,
+                policy.show("x", window.position.x),
+                policy.show("y", window.position.y)
+            );
+            return painter.paint(&line, THEME.zero_size);
         }
 
-        let title = Self::truncate_title(&window.title, config.max_title_length);
         format!(
             "[{}] {} (PID: {}) @ ({},{})",
-            window.index, title, window.pid, window.position.x, window.position.y
+            policy.show("index", window.index),
+            painter.process(&title, &window.process_name),
+            painter.paint(&pid, THEME.pid),
+            policy.show("x", window.position.x),
+            policy.show("y", window.position.y)
         )
     }
 
-    // Detailed format - single window
-    fn format_detail(window: &WindowInfo, _config: &FormatConfig) -> String {
-        format!(
-            "Index: {}\n\
-             Handle: 0x{:x}\n\
-             PID: {}\n\
-             Title: {}\n\
-             Class: {}\n\
-             Process: {}\n\
-             File: {}\n\
-             Position: ({}, {}) Size: {}x{}\n\
-             {}",
-            window.index,
-            window.hwnd,
-            window.pid,
-            window.title,
-            window.class_name,
-            window.process_name,
-            window.process_file.display(),
-            window.position.x,
-            window.position.y,
-            window.position.width,
-            window.position.height,
-            "-".repeat(40)
-        )
+    // Detailed format - single window; dropped fields are left out
+    fn format_detail(window: &WindowInfo, config: &FormatConfig) -> String {
+        let painter = Painter::new(config.color);
+        let policy = &config.redaction;
+        let label = |text: &str| painter.paint(text, THEME.label);
+        let title = if Self::is_zero_size(window, config) {
+            painter.paint(&window.title, THEME.zero_size)
+        } else {
+            painter.process(&window.title, &window.process_name)
+        };
+        let p = &window.position;
+
+        let fields = [
+            ("index", "Index:", window.index.to_string()),
+            (
+                "hwnd",
+                "Handle:",
+                painter.paint(&format!("0x{:x}", window.hwnd), THEME.handle),
+            ),
+            (
+                "pid",
+                "PID:",
+                painter.paint(&window.pid.to_string(), THEME.pid),
+            ),
+            ("title", "Title:", title),
+            ("class", "Class:", window.class_name.clone()),
+            ("process", "Process:", window.process_name.clone()),
+            ("file", "File:", window.process_file.display().to_string()),
+        ];
+        let mut lines: Vec<String> = fields
+            .into_iter()
+            .filter(|(field, ..)| !policy.drops(field))
+            .map(|(_, name, value)| format!("{} {}", label(name), value))
+            .collect();
+
+        // Position and size share a line; a pair is left out only if both halves are dropped
+        let mut geometry = Vec::new();
+        if !(policy.drops("x") && policy.drops("y")) {
+            geometry.push(format!(
+                "{} ({}, {})",
+                label("Position:"),
+                policy.show("x", p.x),
+                policy.show("y", p.y)
+            ));
+        }
+        if !(policy.drops("width") && policy.drops("height")) {
+            geometry.push(format!(
+                "{} {}x{}",
+                label("Size:"),
+                policy.show("width", p.width),
+                policy.show("height", p.height)
+            ));
+        }
+        if !geometry.is_empty() {
+            lines.push(geometry.join(" "));
+        }
+
+        lines.push("-".repeat(40));
+        lines.join("\n")
     }
 
     // Table format - list
     fn format_table(windows: &[WindowInfo], config: &FormatConfig) -> String {
+        if config.table.show_file || config.table.width != TableWidth::Unlimited {
+            return Self::format_table_fitted(windows, config);
+        }
+
         let mut output = String::new();
 
+        let painter = Painter::new(config.color);
+        let fixed = FixedColumns::new(&config.redaction);
+
         // Header
         if config.show_headers {
-            output.push_str(&format!(
-                "{:<6} {:<12} {:<8} {:<12} {}\n",
-                "Index", "Handle", "PID", "Position", "Title"
-            ));
-            output.push_str(&format!(
-                "{:-<6} {:-<12} {:-<8} {:-<12} {:-<30}\n",
-                "", "", "", "", ""
-            ));
+            let header = fixed.header() + "Title";
+            output.push_str(&painter.paint(&header, THEME.header));
+            output.push('\n');
+            output.push_str(&fixed.separator());
+            output.push_str(&"-".repeat(30));
+            output.push('\n');
         }
 
         // Rows
+        // Pad cells before painting so escape codes do not affect alignment
         for window in windows {
-            let title = Self::truncate_title(&window.title, config.max_title_length);
-            output.push_str(&format!(
-                "{:<6} 0x{:<10x} {:<8} {:4},{:<7} {}\n",
-                window.index, window.hwnd, window.pid, window.position.x, window.position.y, title
-            ));
+            let title = Self::display_title(window, config);
+            if Self::is_zero_size(window, config) {
+                let row = fixed.cells(window, &config.redaction, None) + &title;
+                output.push_str(&painter.paint(&row, THEME.zero_size));
+            } else {
+                output.push_str(&fixed.cells(window, &config.redaction, Some(painter)));
+                output.push_str(&painter.process(&title, &window.process_name));
+            }
+            output.push('\n');
         }
 
         output
     }
 
+    // Table format - list split into pages, each ending with a "showing 1–50 of 312" footer
+    fn format_table_pages(
+        windows: &[WindowInfo],
+        total: usize,
+        offset: usize,
+        config: &FormatConfig,
+    ) -> String {
+        if config.page_size.is_none() && windows.len() == total {
+            return Self::format_table(windows, config);
+        }
+
+        // Configs built without validation may ask for empty pages
+        let page_size = config.page_size.unwrap_or(windows.len()).max(1);
+        windows
+            .chunks(page_size)
+            .enumerate()
+            .map(|(page, rows)| {
+                let first = offset + page * page_size + 1;
+                let last = first + rows.len() - 1;
+                format!(
+                    "{}showing {}–{} of {}\n",
+                    Self::format_table(rows, config),
+                    first,
+                    last,
+                    total
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     // Table format - single window
-    fn format_table_single(window: &WindowInfo) -> String {
-        Self::format_table(std::slice::from_ref(window), &FormatConfig::default())
+    fn format_table_single(window: &WindowInfo, config: &FormatConfig) -> String {
+        let config = FormatConfig {
+            color: config.color,
+            table: config.table.clone(),
+            redaction: config.redaction.clone(),
+            ..Default::default()
+        };
+        Self::format_table(std::slice::from_ref(window), &config)
     }
 
     // CSV format - list
-    fn format_csv(windows: &[WindowInfo], config: &FormatConfig) -> String {
+    fn format_csv(windows: &[WindowInfo], config: &FormatConfig, dialect: &CsvDialect) -> String {
         let mut output = String::new();
 
+        if dialect.bom {
+            output.push(BOM);
+        }
+
+        // Columns follow TEMPLATE_FIELDS, minus those dropped by the redaction policy
+        let kept = |fields: [String; 11]| -> Vec<String> {
+            TEMPLATE_FIELDS
+                .iter()
+                .zip(fields)
+                .filter(|(name, _)| !config.redaction.drops(name))
+                .map(|(_, field)| field)
+                .collect()
+        };
+
         if config.show_headers {
-            output.push_str("Index,Handle,PID,Title,Class,Process,File,X,Y,Width,Height\n");
+            dialect.write_record(
+                &mut output,
+                &kept(
+                    [
+                        "Index", "Handle", "PID", "Title", "Class", "Process", "File", "X", "Y",
+                        "Width", "Height",
+                    ]
+                    .map(String::from),
+                ),
+            );
         }
 
         for window in windows {
-            let title = Self::escape_csv_field(&window.title);
-            let class_name = Self::escape_csv_field(&window.class_name);
-            let process_name = Self::escape_csv_field(&window.process_name);
-            let file_path = Self::escape_csv_field(&window.process_file.to_string_lossy());
-
-            output.push_str(&format!(
-                "{},{},{},{},{},{},{},{},{},{},{}\n",
-                window.index,
-                window.hwnd,
-                window.pid,
-                title,
-                class_name,
-                process_name,
-                file_path,
-                window.position.x,
-                window.position.y,
-                window.position.width,
-                window.position.height
-            ));
+            let text = |field: &str| Self::spreadsheet_text(field, config);
+            dialect.write_record(
+                &mut output,
+                &kept([
+                    window.index.to_string(),
+                    window.hwnd.to_string(),
+                    window.pid.to_string(),
+                    text(&window.title),
+                    text(&window.class_name),
+                    text(&window.process_name),
+                    text(&window.process_file.to_string_lossy()),
+                    window.position.x.to_string(),
+                    window.position.y.to_string(),
+                    window.position.width.to_string(),
+                    window.position.height.to_string(),
+                ]),
+            );
         }
 
         output
     }
 
     // CSV format - single window
-    fn format_csv_single(window: &WindowInfo) -> String {
-        Self::format_csv(std::slice::from_ref(window), &FormatConfig::default())
+    fn format_csv_single(
+        window: &WindowInfo,
+        config: &FormatConfig,
+        dialect: &CsvDialect,
+    ) -> String {
+        let config = FormatConfig {
+            escape_formulas: config.escape_formulas,
+            redaction: config.redaction.clone(),
+            ..Default::default()
+        };
+        Self::format_csv(std::slice::from_ref(window), &config, dialect)
+    }
+
+    // Text cell for formats that end up in spreadsheets
+    pub(crate) fn spreadsheet_text(field: &str, config: &FormatConfig) -> String {
+        if config.escape_formulas {
+            neutralize_formula(field).into_owned()
+        } else {
+            field.to_string()
+        }
     }
 
     // Simple format list
@@ -236,7 +1241,7 @@ impl WindowFormatter {
     // Custom template formatting
     fn format_custom(window: &WindowInfo, config: &FormatConfig) -> String {
         if let Some(template) = &config.template {
-            Self::apply_template(window, template)
+            Self::apply_template(window, template, config)
         } else {
             Self::format_simple(window, config)
         }
@@ -252,69 +1257,101 @@ impl WindowFormatter {
     }
 
     // Apply template
-    fn apply_template(window: &WindowInfo, template: &TemplateFormat) -> String {
+    fn apply_template(
+        window: &WindowInfo,
+        template: &TemplateFormat,
+        config: &FormatConfig,
+    ) -> String {
         match template {
-            TemplateFormat::Fields(fields) => Self::format_fields(window, fields),
-            TemplateFormat::KeyValue(fields) => Self::format_key_value(window, fields),
+            TemplateFormat::Fields(fields) => Self::format_fields(window, fields, config),
+            TemplateFormat::KeyValue(fields) => Self::format_key_value(window, fields, config),
             TemplateFormat::Custom(template_str) => {
-                Self::format_custom_template(window, template_str)
+                Self::format_custom_template(window, template_str, config)
             }
         }
     }
 
     // Output only field values
-    fn format_fields(window: &WindowInfo, fields: &[String]) -> String {
+    fn format_fields(window: &WindowInfo, fields: &[String], config: &FormatConfig) -> String {
         let values: Vec<String> = fields
             .iter()
-            .map(|field| Self::get_field_value(window, field))
+            .map(|field| Self::get_field_value(window, field, config))
             .collect();
 
         values.join("\t")
     }
 
     // Output field names and values
-    fn format_key_value(window: &WindowInfo, fields: &[String]) -> String {
+    fn format_key_value(window: &WindowInfo, fields: &[String], config: &FormatConfig) -> String {
         fields
             .iter()
             .map(|field| {
-                let value = Self::get_field_value(window, field);
+                let value = Self::get_field_value(window, field, config);
                 format!("{}: {}", field, value)
             })
             .collect::<Vec<_>>()
             .join(" | ")
     }
 
-    // Custom template string
-    fn format_custom_template(window: &WindowInfo, template: &str) -> String {
-        let mut result = template.to_string();
-
-        // Replace template variables
-        let replacements = [
-            ("{index}", &window.index.to_string()),
-            ("{hwnd}", &format!("0x{:x}", window.hwnd)),
-            ("{pid}", &window.pid.to_string()),
-            ("{title}", &window.title),
-            ("{class}", &window.class_name),
-            ("{process}", &window.process_name),
-            (
-                "{file}",
-                &window.process_file.to_string_lossy().into_owned(),
-            ),
-            ("{x}", &window.position.x.to_string()),
-            ("{y}", &window.position.y.to_string()),
-            ("{width}", &window.position.width.to_string()),
-            ("{height}", &window.position.height.to_string()),
-        ];
+    // Custom template string; `{field|style|...}` applies styles such as `color:red` or `bold`
+    fn format_custom_template(
+        window: &WindowInfo,
+        template: &str,
+        config: &FormatConfig,
+    ) -> String {
+        let painter = Painter::new(config.color);
+        let mut result = String::with_capacity(template.len());
+        let mut rest = template;
 
-        for (pattern, replacement) in replacements {
-            result = result.replace(pattern, replacement);
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let replaced = after.find('}').and_then(|end| {
+                let value = Self::render_placeholder(window, &after[..end], config, painter)?;
+                Some((end, value))
+            });
+            match replaced {
+                Some((end, value)) => {
+                    result.push_str(&value);
+                    rest = &after[end + 1..];
+                }
+                // Unknown placeholders are kept as written
+                None => {
+                    result.push('{');
+                    rest = after;
+                }
+            }
         }
 
+        result.push_str(rest);
         result
     }
 
-    // Get field value
-    fn get_field_value(window: &WindowInfo, field: &str) -> String {
+    // Value of a placeholder with its styles applied, or None for unknown fields
+    fn render_placeholder(
+        window: &WindowInfo,
+        placeholder: &str,
+        config: &FormatConfig,
+        painter: Painter,
+    ) -> Option<String> {
+        let mut parts = placeholder.split('|');
+        let field = parts.next()?;
+        if !TEMPLATE_FIELDS.contains(&field.to_lowercase().as_str()) {
+            return None;
+        }
+
+        let mut value = Self::get_field_value(window, field, config);
+        for sgr in parts.filter_map(template_style) {
+            value = painter.paint(&value, sgr);
+        }
+        Some(value)
+    }
+
+    // Get field value, or the placeholder if it is dropped
+    fn get_field_value(window: &WindowInfo, field: &str, config: &FormatConfig) -> String {
+        if config.redaction.drops(field) {
+            return REDACTED.to_string();
+        }
         match field.to_lowercase().as_str() {
             "index" => window.index.to_string(),
             "hwnd" => format!("0x{:x}", window.hwnd),
@@ -332,10 +1369,11 @@ impl WindowFormatter {
     }
 
     // Utility functions
-    fn truncate_title(title: &str, max_length: Option<usize>) -> String {
+    pub(crate) fn truncate_title(title: &str, max_length: Option<usize>) -> String {
         if let Some(max) = max_length {
-            if title.len() > max {
-                format!("{}...", &title[..max - 3])
+            if title.chars().count() > max {
+                let kept: String = title.chars().take(max.saturating_sub(3)).collect();
+                format!("{}...", kept)
             } else {
                 title.to_string()
             }
@@ -344,12 +1382,24 @@ impl WindowFormatter {
         }
     }
 
-    fn escape_csv_field(field: &str) -> String {
-        if field.contains(',') || field.contains('"') || field.contains('\n') {
-            format!("\"{}\"", field.replace('"', "\"\""))
-        } else {
-            field.to_string()
-        }
+    // Title cut to `max_title_length`, or the placeholder if it is dropped
+    pub(crate) fn display_title(window: &WindowInfo, config: &FormatConfig) -> String {
+        config.redaction.show(
+            "title",
+            Self::truncate_title(&window.title, config.max_title_length),
+        )
+    }
+
+    // Whether the window has no area; unknown when its size is dropped
+    pub(crate) fn is_zero_size(window: &WindowInfo, config: &FormatConfig) -> bool {
+        !config.redaction.drops_size() && window.position.area() == 0
+    }
+
+    // FNV-1a hash, stable across runs and platforms unlike `DefaultHasher`
+    pub(crate) fn stable_hash(text: &str) -> u64 {
+        text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
     }
 }
 
@@ -358,6 +1408,61 @@ pub trait WindowListFormat {
     /// Format windows according to the configuration.
     fn format_output(&self, config: &FormatConfig) -> String;
 
+    /// Format windows according to the configuration, failing if a path is
+    /// refused by [`PathEncoding::Error`].
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # {
+    /// use std::ffi::OsStr;
+    /// use std::os::unix::ffi::OsStrExt;
+    /// use std::path::PathBuf;
+    /// use window_enumerator_formatter::{
+    ///     FormatConfig, FormatError, OutputFormat, PathEncoding, WindowInfo, WindowListFormat,
+    /// };
+    ///
+    /// let file = PathBuf::from(OsStr::from_bytes(b"/home/al\xffice/app"));
+    /// let windows = vec![WindowInfo::builder().process_file(file).build()];
+    /// let config = FormatConfig::builder()
+    ///     .format(OutputFormat::Json)
+    ///     .path_encoding(PathEncoding::Error)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(matches!(
+    ///     windows.try_format_output(&config),
+    ///     Err(FormatError::InvalidPath { .. })
+    /// ));
+    /// # }
+    /// ```
+    fn try_format_output(&self, config: &FormatConfig) -> Result<String, FormatError>;
+
+    /// Format windows as bytes according to the configuration, with text
+    /// formats in [`FormatConfig::encoding`].
+    fn format_bytes(&self, config: &FormatConfig) -> Vec<u8>;
+
+    /// Write windows as bytes to `writer`, as [`format_bytes`](Self::format_bytes) returns them.
+    /// Fails before writing anything if a path is refused by [`PathEncoding::Error`].
+    ///
+    /// ```
+    /// use window_enumerator_formatter::{
+    ///     FormatConfig, OutputEncoding, OutputFormat, WindowInfo, WindowListFormat,
+    /// };
+    ///
+    /// let windows = vec![WindowInfo::builder().title("Café".to_string()).build()];
+    /// let config = FormatConfig::builder()
+    ///     .format(OutputFormat::Csv)
+    ///     .encoding(OutputEncoding::Utf16Le)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let mut file = Vec::new();
+    /// windows.write_bytes(&config, &mut file).unwrap();
+    /// assert!(file.starts_with(&[0xff, 0xfe, b'I', 0, b'n', 0]));
+    /// ```
+    fn write_bytes(&self, config: &FormatConfig, writer: &mut dyn Write)
+        -> Result<(), FormatError>;
+
     /// Format windows with a specific output format.
     fn format_with(&self, format: OutputFormat) -> String;
 }
@@ -367,6 +1472,22 @@ impl WindowListFormat for [WindowInfo] {
         WindowFormatter::format_windows(self, config)
     }
 
+    fn try_format_output(&self, config: &FormatConfig) -> Result<String, FormatError> {
+        WindowFormatter::try_format_windows(self, config)
+    }
+
+    fn format_bytes(&self, config: &FormatConfig) -> Vec<u8> {
+        WindowFormatter::format_windows_bytes(self, config)
+    }
+
+    fn write_bytes(
+        &self,
+        config: &FormatConfig,
+        writer: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        WindowFormatter::write_windows_bytes(self, config, writer)
+    }
+
     fn format_with(&self, format: OutputFormat) -> String {
         let config = FormatConfig {
             format,
@@ -381,6 +1502,22 @@ impl WindowListFormat for Vec<WindowInfo> {
         WindowFormatter::format_windows(self, config)
     }
 
+    fn try_format_output(&self, config: &FormatConfig) -> Result<String, FormatError> {
+        WindowFormatter::try_format_windows(self, config)
+    }
+
+    fn format_bytes(&self, config: &FormatConfig) -> Vec<u8> {
+        WindowFormatter::format_windows_bytes(self, config)
+    }
+
+    fn write_bytes(
+        &self,
+        config: &FormatConfig,
+        writer: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        WindowFormatter::write_windows_bytes(self, config, writer)
+    }
+
     fn format_with(&self, format: OutputFormat) -> String {
         let config = FormatConfig {
             format,
@@ -0,0 +1,161 @@
+//! Syntax highlighting for JSON and YAML output.
+
+use crate::color::{Painter, THEME};
+
+/// Highlight serialized JSON: keys, strings, numbers and `true`/`false`/`null`.
+pub(crate) fn highlight_json(json: &str, painter: Painter) -> String {
+    let mut output = String::with_capacity(json.len() * 2);
+    let mut rest = json;
+
+    while let Some(c) = rest.chars().next() {
+        let token_len = match c {
+            '"' => string_len(rest),
+            '-' | '0'..='9' => rest
+                .find(|c: char| !matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))
+                .unwrap_or(rest.len()),
+            c if c.is_ascii_alphabetic() => rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len()),
+            _ => {
+                output.push(c);
+                rest = &rest[c.len_utf8()..];
+                continue;
+            }
+        };
+
+        let (token, after) = rest.split_at(token_len);
+        let sgr = match c {
+            '"' if after.trim_start().starts_with(':') => THEME.key,
+            '"' => THEME.string,
+            '-' | '0'..='9' => THEME.number,
+            _ => THEME.literal,
+        };
+        output.push_str(&painter.paint(token, sgr));
+        rest = after;
+    }
+
+    output
+}
+
+// Length of the JSON string literal at the start of `text`, including quotes
+fn string_len(text: &str) -> usize {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return i + 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// Highlight serialized YAML line by line: mapping keys and scalar values.
+/// Lines of `|` and `>` block scalars are strings, whatever they contain.
+pub(crate) fn highlight_yaml(yaml: &str, painter: Painter) -> String {
+    let mut output = String::with_capacity(yaml.len() * 2);
+    // Indentation of the line that opened the current block scalar
+    let mut block: Option<usize> = None;
+
+    for line in yaml.split_inclusive('\n') {
+        let (line, newline) = match line.strip_suffix('\n') {
+            Some(line) => (line, "\n"),
+            None => (line, ""),
+        };
+        let content = line.trim_start_matches(' ');
+        let indent = line.len() - content.len();
+
+        match block {
+            Some(parent) if content.is_empty() || indent > parent => {
+                output.push_str(&line[..indent]);
+                output.push_str(&painter.paint(content, THEME.string));
+            }
+            _ => {
+                let (highlighted, opened) = highlight_yaml_line(line, painter);
+                output.push_str(&highlighted);
+                block = opened;
+            }
+        }
+        output.push_str(newline);
+    }
+    output
+}
+
+// A highlighted line, and the indentation of the block scalar it opens: that of
+// its key, or of the line for a bare `- |` sequence item
+fn highlight_yaml_line(line: &str, painter: Painter) -> (String, Option<usize>) {
+    let content = line.trim_start_matches([' ', '-']);
+    let indent = &line[..line.len() - content.len()];
+
+    let mut output = indent.to_string();
+    let is_quoted = content.starts_with(['"', '\'']);
+    let split = if is_quoted {
+        None
+    } else if let Some(key) = content.strip_suffix(':') {
+        Some((key, ""))
+    } else {
+        content.split_once(": ")
+    };
+
+    let parent = match split {
+        Some(_) => indent.len(),
+        None => line.len() - line.trim_start_matches(' ').len(),
+    };
+    let value = match split {
+        Some((key, value)) => {
+            output.push_str(&painter.paint(key, THEME.key));
+            output.push(':');
+            if !value.is_empty() {
+                output.push(' ');
+            }
+            value
+        }
+        None => content,
+    };
+    let opens_block = is_block_indicator(value);
+    if opens_block {
+        output.push_str(value);
+    } else {
+        output.push_str(&yaml_scalar(value, painter));
+    }
+    (output, opens_block.then_some(parent))
+}
+
+// `|` or `>`, with optional chomping and indentation indicators such as `|-` or `>2+`
+fn is_block_indicator(value: &str) -> bool {
+    value
+        .strip_prefix(['|', '>'])
+        .is_some_and(|rest| rest.chars().all(|c| matches!(c, '-' | '+' | '1'..='9')))
+}
+
+fn yaml_scalar(value: &str, painter: Painter) -> String {
+    let sgr = match value {
+        "true" | "false" | "null" | "~" => THEME.literal,
+        _ if value.parse::<f64>().is_ok() => THEME.number,
+        "" | "---" | "[]" | "{}" => return value.to_string(),
+        _ => THEME.string,
+    };
+    painter.paint(value, sgr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::ColorChoice;
+
+    #[test]
+    fn yaml_block_scalars_are_strings() {
+        let painter = Painter::new(ColorChoice::Always);
+        let yaml = "- title: |-\n    a: b\n\n    c: d\n  pid: 1\n";
+        let expected = format!(
+            "- {}: |-\n    {}\n\n    {}\n  {}: {}\n",
+            painter.paint("title", THEME.key),
+            painter.paint("a: b", THEME.string),
+            painter.paint("c: d", THEME.string),
+            painter.paint("pid", THEME.key),
+            painter.paint("1", THEME.number),
+        );
+        assert_eq!(highlight_yaml(yaml, painter), expected);
+    }
+}
@@ -0,0 +1,281 @@
+//! Self-contained HTML report output.
+
+use crate::formatter::{FormatConfig, WindowFormatter};
+use crate::models::WindowInfo;
+use crate::redact::{RedactionPolicy, REDACTED};
+use crate::summary::WindowSummary;
+use crate::svg::SvgOptions;
+use serde::{Deserialize, Serialize};
+
+/// Options for [`OutputFormat::Html`](crate::OutputFormat::Html).
+///
+/// ```
+/// use window_enumerator_formatter::{OutputFormat, WindowInfo, WindowListFormat, WindowPosition};
+///
+/// let windows = vec![WindowInfo::builder()
+///     .index(1)
+///     .hwnd(0x10318)
+///     .pid(4242)
+///     .title("Tom & Jerry".to_string())
+///     .class_name("Chrome_WidgetWin_1".to_string())
+///     .process_name("chrome.exe".to_string())
+///     .process_file("chrome.exe".into())
+///     .position(WindowPosition { x: 0, y: 0, width: 1200, height: 800 })
+///     .build()];
+///
+/// let html = windows.format_with(OutputFormat::Html);
+/// assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n"));
+/// assert!(html.contains(
+///     "<section>\n<h2>Summary</h2>\n\
+///      <p>1 windows from 1 processes, 0 with zero size.</p>\n\
+///      <table>\n<thead><tr><th>Process</th><th>Windows</th><th>Area (px)</th></tr></thead>\n\
+///      <tbody>\n<tr><td>chrome.exe</td><td class=\"number\">1</td><td class=\"number\">960000</td></tr>\n\
+///      </tbody>\n</table>\n</section>\n"
+/// ));
+/// // Sort keys of formatted cells are kept in `data-value`
+/// assert!(html.contains(
+///     "<tr><td class=\"number\">1</td>\
+///      <td class=\"number\" data-value=\"66328\">0x10318</td>\
+///      <td class=\"number\">4242</td>\
+///      <td title=\"Tom &amp; Jerry\">Tom &amp; Jerry</td>\
+///      <td>Chrome_WidgetWin_1</td><td>chrome.exe</td><td>chrome.exe</td>\
+///      <td class=\"number\" data-value=\"0\">0,0</td>\
+///      <td class=\"number\" data-value=\"960000\">1200x800</td></tr>\n"
+/// ));
+/// assert!(html.ends_with("</script>\n</body>\n</html>\n"));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HtmlOptions {
+    /// Page title and top-level heading.
+    pub page_title: String,
+    /// Whether to include an SVG preview of the window rectangles.
+    pub svg_preview: bool,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        Self {
+            page_title: "Window Report".to_string(),
+            svg_preview: false,
+        }
+    }
+}
+
+/// Width in pixels of the SVG preview.
+const PREVIEW_WIDTH: u32 = 960;
+
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+th { background: #f0f0f0; }
+#windows th { cursor: pointer; user-select: none; }
+#windows th[data-order=asc]::after { content: \" \\25B2\"; }
+#windows th[data-order=desc]::after { content: \" \\25BC\"; }
+td.number { text-align: right; font-variant-numeric: tabular-nums; }
+tbody tr:nth-child(even) { background: #fafafa; }
+#filter { padding: 0.3em; width: 20em; }
+svg { max-width: 100%; height: auto; border: 1px solid #ccc; }";
+
+const SCRIPT: &str = "\
+const table = document.getElementById('windows');
+const body = table.tBodies[0];
+document.getElementById('filter').addEventListener('input', (event) => {
+  const needle = event.target.value.toLowerCase();
+  for (const row of body.rows) {
+    row.hidden = !row.textContent.toLowerCase().includes(needle);
+  }
+});
+table.querySelectorAll('th').forEach((th, column) => th.addEventListener('click', () => {
+  const numeric = th.dataset.type === 'number';
+  const ascending = th.dataset.order !== 'asc';
+  table.querySelectorAll('th').forEach((other) => delete other.dataset.order);
+  th.dataset.order = ascending ? 'asc' : 'desc';
+  const key = (row) => {
+    const cell = row.cells[column];
+    const value = cell.dataset.value ?? cell.textContent;
+    return numeric ? Number(value) : value.toLowerCase();
+  };
+  const rows = Array.from(body.rows).sort((a, b) => {
+    const [x, y] = [key(a), key(b)];
+    return (x < y ? -1 : x > y ? 1 : 0) * (ascending ? 1 : -1);
+  });
+  body.append(...rows);
+}));";
+
+// Column headers, and whether each column sorts numerically
+const COLUMNS: [(&str, bool); 9] = [
+    ("Index", true),
+    ("Handle", true),
+    ("PID", true),
+    ("Title", false),
+    ("Class", false),
+    ("Process", false),
+    ("File", false),
+    ("Position", true),
+    ("Size", true),
+];
+
+impl WindowFormatter {
+    // HTML format - list
+    pub(crate) fn format_html(windows: &[WindowInfo], config: &FormatConfig) -> String {
+        let options = &config.html;
+        let page_title = escape_html(&options.page_title);
+
+        let mut output = String::new();
+        output.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+        output.push_str("<meta charset=\"utf-8\">\n");
+        output.push_str(&format!("<title>{}</title>\n", page_title));
+        output.push_str(&format!("<style>\n{}\n</style>\n", STYLE));
+        output.push_str("</head>\n<body>\n");
+        output.push_str(&format!("<h1>{}</h1>\n", page_title));
+
+        output.push_str(&Self::html_summary(
+            &WindowSummary::from_windows(windows),
+            &config.redaction,
+        ));
+        if options.svg_preview {
+            output.push_str("<section>\n<h2>Layout</h2>\n");
+            let preview = SvgOptions {
+                max_width: Some(PREVIEW_WIDTH),
+                ..config.svg.clone()
+            };
+            output.push_str(&Self::svg_element(windows, config, &preview));
+            output.push_str("</section>\n");
+        }
+        output.push_str(&Self::html_table(windows, config));
+
+        output.push_str(&format!("<script>\n{}\n</script>\n", SCRIPT));
+        output.push_str("</body>\n</html>\n");
+        output
+    }
+
+    // Summary section with per-process counts; areas are left out when sizes are dropped
+    fn html_summary(summary: &WindowSummary, policy: &RedactionPolicy) -> String {
+        let sized = !policy.drops_size();
+        let mut output = String::from("<section>\n<h2>Summary</h2>\n");
+        output.push_str(&format!(
+            "<p>{} windows from {} processes",
+            summary.total,
+            summary.processes.len()
+        ));
+        if sized {
+            output.push_str(&format!(", {} with zero size", summary.zero_size));
+        }
+        output.push_str(".</p>\n<table>\n<thead><tr><th>Process</th><th>Windows</th>");
+        if sized {
+            output.push_str("<th>Area (px)</th>");
+        }
+        output.push_str("</tr></thead>\n<tbody>\n");
+        for process in &summary.processes {
+            output.push_str(&format!(
+                "<tr><td>{}</td><td class=\"number\">{}</td>",
+                escape_html(&policy.show("process", &process.process)),
+                process.count
+            ));
+            if sized {
+                output.push_str(&format!("<td class=\"number\">{}</td>", process.area));
+            }
+            output.push_str("</tr>\n");
+        }
+        output.push_str("</tbody>\n</table>\n</section>\n");
+        output
+    }
+
+    // Sortable, filterable window table
+    fn html_table(windows: &[WindowInfo], config: &FormatConfig) -> String {
+        let mut output = String::from("<section>\n<h2>Windows</h2>\n");
+        output
+            .push_str("<input id=\"filter\" type=\"search\" placeholder=\"Filter windows...\">\n");
+        output.push_str("<table id=\"windows\">\n<thead><tr>");
+        for (name, numeric) in COLUMNS {
+            if numeric {
+                output.push_str(&format!("<th data-type=\"number\">{}</th>", name));
+            } else {
+                output.push_str(&format!("<th>{}</th>", name));
+            }
+        }
+        output.push_str("</tr></thead>\n<tbody>\n");
+
+        let policy = &config.redaction;
+        for window in windows {
+            let position = &window.position;
+            // Numeric cells keep their sort key in `data-value`; dropped cells have none
+            let number = |fields: &[&str], key: i64, text: String| {
+                if fields.iter().any(|field| policy.drops(field)) {
+                    format!("<td class=\"number\">{}</td>", escape_html(&text))
+                } else {
+                    format!("<td class=\"number\" data-value=\"{}\">{}</td>", key, text)
+                }
+            };
+            let text = |field: &str, value: &str| {
+                format!("<td>{}</td>", escape_html(&policy.show(field, value)))
+            };
+            let title = if policy.drops("title") {
+                format!("<td>{}</td>", escape_html(REDACTED))
+            } else {
+                format!(
+                    "<td title=\"{}\">{}</td>",
+                    escape_html(&window.title),
+                    escape_html(&Self::truncate_title(
+                        &window.title,
+                        config.max_title_length
+                    ))
+                )
+            };
+
+            output.push_str(&format!(
+                "<tr><td class=\"number\">{}</td>{}<td class=\"number\">{}</td>{}{}{}{}{}{}</tr>\n",
+                escape_html(&policy.show("index", window.index)),
+                number(
+                    &["hwnd"],
+                    window.hwnd as i64,
+                    policy.show("hwnd", format_args!("0x{:x}", window.hwnd))
+                ),
+                escape_html(&policy.show("pid", window.pid)),
+                title,
+                text("class", &window.class_name),
+                text("process", &window.process_name),
+                text("file", &window.process_file.to_string_lossy()),
+                number(
+                    &["x"],
+                    position.x as i64,
+                    format!(
+                        "{},{}",
+                        policy.show("x", position.x),
+                        policy.show("y", position.y)
+                    )
+                ),
+                number(
+                    &["width", "height"],
+                    position.area() as i64,
+                    format!(
+                        "{}x{}",
+                        policy.show("width", position.width),
+                        policy.show("height", position.height)
+                    )
+                ),
+            ));
+        }
+
+        output.push_str("</tbody>\n</table>\n</section>\n");
+        output
+    }
+}
+
+/// Escape text for HTML and XML element content and quoted attribute values.
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
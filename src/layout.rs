@@ -0,0 +1,442 @@
+//! Terminal-width-aware table layout.
+
+use crate::color::{Painter, THEME};
+use crate::formatter::{FormatConfig, WindowFormatter};
+use crate::models::WindowInfo;
+use crate::redact::{RedactionPolicy, REDACTED};
+use serde::{Deserialize, Serialize};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Target width of [`OutputFormat::Table`](crate::OutputFormat::Table) output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TableWidth {
+    /// No limit; long titles extend the line.
+    #[default]
+    Unlimited,
+    /// Use `COLUMNS`, then the terminal size (with the `terminal-size` feature),
+    /// then 80 columns.
+    Auto,
+    /// Fit into the given number of columns.
+    Fixed(usize),
+}
+
+impl TableWidth {
+    /// Resolve to a column count, or `None` for unlimited width.
+    pub fn resolve(self) -> Option<usize> {
+        match self {
+            TableWidth::Unlimited => None,
+            TableWidth::Fixed(width) => Some(width),
+            TableWidth::Auto => Some(detect_width()),
+        }
+    }
+}
+
+fn detect_width() -> usize {
+    if let Some(columns) = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&columns: &usize| columns > 0)
+    {
+        return columns;
+    }
+
+    #[cfg(feature = "terminal-size")]
+    if let Some((terminal_size::Width(width), _)) = terminal_size::terminal_size() {
+        return width as usize;
+    }
+
+    DEFAULT_WIDTH
+}
+
+/// How cells wider than their column are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Overflow {
+    /// Cut the cell and end it with `...`.
+    #[default]
+    Truncate,
+    /// Word-wrap the cell onto continuation lines. Titles are wrapped in full,
+    /// ignoring [`FormatConfig::max_title_length`].
+    Wrap,
+}
+
+/// Layout options for [`OutputFormat::Table`](crate::OutputFormat::Table).
+///
+/// ```
+/// use window_enumerator_formatter::{
+///     ColorChoice, FormatConfig, OutputFormat, Overflow, TableOptions, TableWidth, WindowInfo,
+///     WindowListFormat, WindowPosition,
+/// };
+///
+/// let windows = vec![WindowInfo::builder()
+///     .index(1)
+///     .hwnd(0x10318)
+///     .pid(4242)
+///     .title("seven words that do not fit the column".to_string())
+///     .process_file(r"C:\Program Files\Editor\editor.exe".into())
+///     .position(WindowPosition { x: 0, y: 0, width: 800, height: 600 })
+///     .build()];
+/// let table = |overflow: Overflow, show_file: bool| {
+///     let config = FormatConfig::builder()
+///         .format(OutputFormat::Table)
+///         .color(ColorChoice::Never)
+///         .max_title_length(Some(20))
+///         .table(TableOptions { width: TableWidth::Fixed(64), overflow, show_file })
+///         .build()
+///         .unwrap();
+///     windows.format_output(&config)
+/// };
+///
+/// assert_eq!(
+///     table(Overflow::Truncate, false),
+///     "\
+/// Index  Handle       PID      Position     Title
+/// ------ ------------ -------- ------------ --------------------
+/// 1      0x10318      4242        0,0       seven words that ...
+/// "
+/// );
+/// assert_eq!(
+///     table(Overflow::Truncate, true),
+///     r"Index  Handle       PID      Position     Title      File
+/// ------ ------------ -------- ------------ ---------- -----------
+/// 1      0x10318      4242        0,0       seven w... C:\Progr...
+/// "
+/// );
+/// // Wrapped titles ignore `max_title_length`
+/// assert_eq!(
+///     table(Overflow::Wrap, false),
+///     "\
+/// Index  Handle       PID      Position     Title
+/// ------ ------------ -------- ------------ ----------------------
+/// 1      0x10318      4242        0,0       seven words that do
+///                                           not fit the column
+/// "
+/// );
+/// assert_eq!(
+///     table(Overflow::Wrap, true),
+///     r"Index  Handle       PID      Position     Title      File
+/// ------ ------------ -------- ------------ ---------- -----------
+/// 1      0x10318      4242        0,0       seven      C:\Program
+///                                           words that Files\Edito
+///                                           do not fit r\editor.ex
+///                                           the column e
+/// "
+/// );
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableOptions {
+    /// Target line width. Only the title and file columns shrink to fit it.
+    pub width: TableWidth,
+    /// How titles and paths wider than their column are shown.
+    pub overflow: Overflow,
+    /// Whether to add a process file column after the title.
+    pub show_file: bool,
+}
+
+/// Width assumed by [`TableWidth::Auto`] when nothing can be detected.
+const DEFAULT_WIDTH: usize = 80;
+
+/// Widths of the index, handle, PID and position columns, widened to fit the
+/// placeholder of dropped fields.
+pub(crate) struct FixedColumns {
+    index: usize,
+    handle: usize,
+    pid: usize,
+    x: usize,
+    y: usize,
+}
+
+impl FixedColumns {
+    pub(crate) fn new(policy: &RedactionPolicy) -> Self {
+        let width = |field: &str, width: usize| {
+            if policy.drops(field) {
+                width.max(REDACTED.len())
+            } else {
+                width
+            }
+        };
+        Self {
+            index: width("index", 6),
+            handle: width("hwnd", 12),
+            pid: width("pid", 8),
+            x: width("x", 4),
+            y: width("y", 7),
+        }
+    }
+
+    /// Width of the columns with their separators.
+    pub(crate) fn width(&self) -> usize {
+        self.index + self.handle + self.pid + self.x + self.y + 5
+    }
+
+    /// Header cells, followed by a space.
+    pub(crate) fn header(&self) -> String {
+        format!(
+            "{:<index$} {:<handle$} {:<pid$} {:<position$} ",
+            "Index",
+            "Handle",
+            "PID",
+            "Position",
+            index = self.index,
+            handle = self.handle,
+            pid = self.pid,
+            position = self.x + 1 + self.y
+        )
+    }
+
+    /// Separator cells, followed by a space.
+    pub(crate) fn separator(&self) -> String {
+        [self.index, self.handle, self.pid, self.x + 1 + self.y]
+            .map(|width| "-".repeat(width) + " ")
+            .concat()
+    }
+
+    /// Cells of a row, followed by a space. Handle and PID are painted with
+    /// `painter`, unless the caller paints the whole row.
+    pub(crate) fn cells(
+        &self,
+        window: &WindowInfo,
+        policy: &RedactionPolicy,
+        painter: Option<Painter>,
+    ) -> String {
+        let handle = format!(
+            "{:<width$}",
+            policy.show("hwnd", format_args!("0x{:x}", window.hwnd)),
+            width = self.handle
+        );
+        let pid = format!(
+            "{:<width$}",
+            policy.show("pid", window.pid),
+            width = self.pid
+        );
+        let (handle, pid) = match painter {
+            Some(painter) => (
+                painter.paint(&handle, THEME.handle),
+                painter.paint(&pid, THEME.pid),
+            ),
+            None => (handle, pid),
+        };
+        format!(
+            "{:<index$} {} {} {:>x$},{:<y$} ",
+            policy.show("index", window.index),
+            handle,
+            pid,
+            policy.show("x", window.position.x),
+            policy.show("y", window.position.y),
+            index = self.index,
+            x = self.x,
+            y = self.y
+        )
+    }
+}
+
+/// Narrowest a flexible column shrinks to.
+const MIN_FLEX_WIDTH: usize = 10;
+
+impl WindowFormatter {
+    // Table format - list, fitted to a width or with a file column
+    pub(crate) fn format_table_fitted(windows: &[WindowInfo], config: &FormatConfig) -> String {
+        let options = &config.table;
+        let policy = &config.redaction;
+        let painter = Painter::new(config.color);
+        let fixed = FixedColumns::new(policy);
+
+        // Wrapped titles are shown in full rather than cut before wrapping
+        let titles: Vec<String> = windows
+            .iter()
+            .map(|w| match options.overflow {
+                Overflow::Truncate => Self::display_title(w, config),
+                Overflow::Wrap => policy.show("title", &w.title),
+            })
+            .collect();
+        let files: Vec<String> = windows
+            .iter()
+            .map(|w| policy.show("file", w.process_file.to_string_lossy()))
+            .collect();
+        let natural = |cells: &[String], header: &str| {
+            cells
+                .iter()
+                .map(|cell| cell.width())
+                .max()
+                .unwrap_or(0)
+                .max(header.len())
+        };
+        let (title_width, file_width) = flexible_widths(
+            natural(&titles, "Title"),
+            options.show_file.then(|| natural(&files, "File")),
+            options.width.resolve(),
+            fixed.width(),
+        );
+
+        let mut output = String::new();
+
+        if config.show_headers {
+            let title_header = if options.show_file {
+                pad("Title", title_width)
+            } else {
+                "Title".to_string()
+            };
+            let mut header = fixed.header() + &title_header;
+            let mut separator = fixed.separator() + &"-".repeat(title_width);
+            if options.show_file {
+                header.push_str(" File");
+                separator.push_str(&format!(" {}", "-".repeat(file_width)));
+            }
+            output.push_str(&painter.paint(&header, THEME.header));
+            output.push('\n');
+            output.push_str(&separator);
+            output.push('\n');
+        }
+
+        for ((window, title), file) in windows.iter().zip(&titles).zip(&files) {
+            let title_lines = fit_cell(title, title_width, options.overflow);
+            let file_lines = if options.show_file {
+                fit_cell(file, file_width, options.overflow)
+            } else {
+                Vec::new()
+            };
+            let zero_size = Self::is_zero_size(window, config);
+
+            for line in 0..title_lines.len().max(file_lines.len()) {
+                let title = title_lines.get(line).map_or("", String::as_str);
+                let cells = if line == 0 {
+                    fixed.cells(window, policy, (!zero_size).then_some(painter))
+                } else {
+                    " ".repeat(fixed.width())
+                };
+                let mut text = if zero_size {
+                    format!("{}{}", cells, title)
+                } else {
+                    format!("{}{}", cells, painter.process(title, &window.process_name))
+                };
+
+                if options.show_file {
+                    let file = file_lines.get(line).map_or("", String::as_str);
+                    text.push_str(&" ".repeat(title_width.saturating_sub(title.width()) + 1));
+                    text.push_str(file);
+                }
+
+                let text = text.trim_end();
+                if zero_size {
+                    output.push_str(&painter.paint(text, THEME.zero_size));
+                } else {
+                    output.push_str(text);
+                }
+                output.push('\n');
+            }
+        }
+
+        output
+    }
+}
+
+// Split the width left after the fixed columns between title and file,
+// shrinking the wider column first
+fn flexible_widths(
+    title: usize,
+    file: Option<usize>,
+    width: Option<usize>,
+    fixed: usize,
+) -> (usize, usize) {
+    let Some(width) = width else {
+        return (title, file.unwrap_or(0));
+    };
+    let available = width.saturating_sub(fixed);
+
+    let Some(file) = file else {
+        return (title.min(available).max(MIN_FLEX_WIDTH), 0);
+    };
+    let available = available.saturating_sub(1);
+    if title + file <= available {
+        return (title, file);
+    }
+
+    let half = available / 2;
+    let (title, file) = if title <= half {
+        (title, available - title)
+    } else if file <= half {
+        (available - file, file)
+    } else {
+        (half, available - half)
+    };
+    (title.max(MIN_FLEX_WIDTH), file.max(MIN_FLEX_WIDTH))
+}
+
+// Lines of a cell fitted to `width` display columns
+fn fit_cell(text: &str, width: usize, overflow: Overflow) -> Vec<String> {
+    if text.width() <= width {
+        return vec![text.to_string()];
+    }
+    match overflow {
+        Overflow::Truncate => vec![truncate_to_width(text, width)],
+        Overflow::Wrap => wrap(text, width),
+    }
+}
+
+// Cut text to `width` display columns, ending with `...`
+pub(crate) fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let ellipsis = if width >= 3 { "..." } else { "" };
+    let mut output = take_width(text, width - ellipsis.len()).to_string();
+    output.push_str(ellipsis);
+    output
+}
+
+// Longest prefix of `text` that fits in `width` display columns
+fn take_width(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (i, c) in text.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used > width {
+            return &text[..i];
+        }
+    }
+    text
+}
+
+// Word-wrap text to `width` display columns, breaking words that do not fit on a line
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split(' ').filter(|word| !word.is_empty()) {
+        let separator = usize::from(!line.is_empty());
+        if line.width() + separator + word.width() <= width {
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+
+        let mut rest = word;
+        while rest.width() > width {
+            let mut head = take_width(rest, width);
+            if head.is_empty() {
+                // A single character wider than the column
+                head = &rest[..rest.chars().next().map_or(rest.len(), char::len_utf8)];
+            }
+            lines.push(head.to_string());
+            rest = &rest[head.len()..];
+        }
+        line.push_str(rest);
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+// Pad `text` with spaces to `width` display columns
+fn pad(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(text.width())))
+}
@@ -5,9 +5,10 @@
 //!
 //! # Features
 //!
-//! - **Multiple Formats**: JSON, YAML, CSV, Table, Simple, and Detailed formats
+//! - **Multiple Formats**: JSON, YAML, MessagePack, CBOR, SQL, Prometheus, CSV, TSV, Table, Markdown, HTML, SVG, Minimap, Simple, and Detailed formats
 //! - **Template System**: Field selection, key-value pairs, and custom templates
 //! - **Highly Configurable**: Title truncation, header control, and output customization
+//! - **Config Files**: Load settings from TOML/YAML/JSON files, `WEF_*` variables and named presets
 //! - **Easy to Use**: Simple API with rich examples and builder pattern
 //! - **Flexible**: Works standalone or with `window-enumerator` integration
 //! - **Optional Dependencies**: Minimal required dependencies
@@ -169,6 +170,513 @@
 //! };
 //!
 //! println!("{}", windows.format_output(&config));
+//!
+//! // The builder rejects contradictory settings
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Csv)
+//!     .max_title_length(None)
+//!     .build()
+//!     .unwrap();
+//! println!("{}", windows.format_output(&config));
+//!
+//! assert!(FormatConfig::builder().format(OutputFormat::Custom).build().is_err());
+//! assert!(FormatConfig::builder().max_title_length(Some(2)).build().is_err());
+//!
+//! // Titles are truncated by characters, never inside a multi-byte character
+//! let window = WindowInfo {
+//!     title: "Привет, мир — это заголовок".to_string(),
+//!     ..windows[0].clone()
+//! };
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Simple)
+//!     .max_title_length(Some(10))
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(window.format(&config), "[1] Привет,... (PID: 1234) @ (0,0)");
+//! ```
+//!
+//! ## Selecting JSON and YAML fields:
+//!
+//! Field lists select the serialized fields, including dotted paths and the
+//! computed fields `area`, `right` and `bottom`.
+//! ```
+//! use window_enumerator_formatter::{
+//!     FormatConfig, OutputFormat, TemplateFormat, WindowInfo, WindowPosition,
+//! };
+//!
+//! let window = WindowInfo::builder()
+//!     .title("Editor".to_string())
+//!     .position(WindowPosition { x: 10, y: 20, width: 300, height: 200 })
+//!     .build();
+//!
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Json)
+//!     .template(TemplateFormat::Fields(vec![
+//!         "title".into(),
+//!         "position.x".into(),
+//!         "area".into(),
+//!     ]))
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(
+//!     window.format(&config),
+//!     r#"{"title":"Editor","position":{"x":10},"area":60000}"#
+//! );
+//!
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Json)
+//!     .template(TemplateFormat::Fields(vec!["title".into(), "position".into()]))
+//!     .flatten_position(true)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(
+//!     window.format(&config),
+//!     r#"{"title":"Editor","x":10,"y":20,"width":300,"height":200}"#
+//! );
+//! ```
+//!
+//! ## Key case and handle representation:
+//! ```
+//! use std::path::PathBuf;
+//! use window_enumerator_formatter::{
+//!     FormatConfig, HandleEncoding, KeyCase, OutputFormat, PathStyle, SerializationOptions,
+//!     Snapshot, TemplateFormat, WindowInfo, WindowListFormat,
+//! };
+//!
+//! let window = WindowInfo::builder()
+//!     .hwnd(0x10318)
+//!     .process_file(PathBuf::from(r"C:\Windows\notepad.exe"))
+//!     .build();
+//!
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Json)
+//!     .template(TemplateFormat::Fields(vec!["hwnd".into(), "file".into()]))
+//!     .serialization(SerializationOptions {
+//!         key_case: KeyCase::Camel,
+//!         handle: HandleEncoding::Hex,
+//!         path_style: PathStyle::Forward,
+//!     })
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(
+//!     window.format(&config),
+//!     r#"{"hwnd":"0x10318","processFile":"C:/Windows/notepad.exe"}"#
+//! );
+//!
+//! // Negative handles are written in two's complement and read back unchanged
+//! let windows = vec![WindowInfo::builder().hwnd(-2).build()];
+//! for format in [OutputFormat::Json, OutputFormat::Yaml, OutputFormat::Xml] {
+//!     let config = FormatConfig::builder()
+//!         .format(format)
+//!         .serialization(SerializationOptions {
+//!             handle: HandleEncoding::Hex,
+//!             ..Default::default()
+//!         })
+//!         .build()
+//!         .unwrap();
+//!     let output = windows.format_output(&config);
+//!     assert!(output.contains("0xfffffffffffffffe") || cfg!(not(target_pointer_width = "64")));
+//!     assert_eq!(Snapshot::parse(&output).unwrap().windows[0].hwnd, -2);
+//! }
+//! ```
+//!
+//! ## Snapshots:
+//!
+//! Envelopes record the schema version, time, host and crate version, and
+//! [`Snapshot::parse`] reads them back along with bare JSON, YAML and NDJSON lists
+//! and single records.
+//! ```
+//! use window_enumerator_formatter::{
+//!     FormatConfig, OutputFormat, Snapshot, WindowInfo, WindowListFormat, SCHEMA_VERSION,
+//! };
+//!
+//! let windows = vec![
+//!     WindowInfo::builder().hwnd(0x10318).title("Editor".to_string()).build(),
+//!     WindowInfo::builder().title("Terminal".to_string()).build(),
+//! ];
+//!
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Ndjson)
+//!     .snapshot(true)
+//!     .build()
+//!     .unwrap();
+//! let ndjson = windows.format_output(&config);
+//! assert_eq!(ndjson.lines().count(), 3);
+//!
+//! let snapshot = Snapshot::parse(&ndjson).unwrap();
+//! assert_eq!(snapshot.schema_version, SCHEMA_VERSION);
+//! assert_eq!(snapshot.windows[0].hwnd, 0x10318);
+//! assert_eq!(snapshot.windows[1].title, "Terminal");
+//!
+//! let bare = Snapshot::parse(&windows.format_with(OutputFormat::Yaml)).unwrap();
+//! assert_eq!(bare.generated_at, None);
+//! assert_eq!(bare.windows.len(), 2);
+//!
+//! // A one-window NDJSON list is a single record
+//! let single = Snapshot::parse(&windows[..1].format_with(OutputFormat::Ndjson)).unwrap();
+//! assert_eq!(single.windows[0].hwnd, 0x10318);
+//!
+//! // Empty lists still get an envelope
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Json)
+//!     .snapshot(true)
+//!     .build()
+//!     .unwrap();
+//! let json = Vec::<WindowInfo>::new().format_output(&config);
+//! assert!(json.ends_with(r#""count":0,"windows":[]}"#));
+//! assert!(Snapshot::parse(&json).unwrap().windows.is_empty());
+//!
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Json)
+//!     .page_envelope(true)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(
+//!     Vec::<WindowInfo>::new().format_output(&config),
+//!     r#"{"total":0,"offset":0,"items":[]}"#
+//! );
+//! ```
+//!
+//! ## XML:
+//! ```
+//! use window_enumerator_formatter::{
+//!     FormatConfig, OutputFormat, Snapshot, WindowInfo, WindowListFormat, WindowPosition, XmlLayout,
+//!     XmlOptions,
+//! };
+//!
+//! let windows = vec![WindowInfo::builder()
+//!     .index(1)
+//!     .hwnd(0x10318)
+//!     .title("Tom & Jerry \x1b[1m".to_string())
+//!     .build()];
+//!
+//! let xml = windows.format_with(OutputFormat::Xml);
+//! assert!(xml.contains(r#"<windows count="1">"#));
+//! assert!(xml.contains(r#"<window index="1" hwnd="0x10318">"#));
+//! // ESC cannot appear in XML 1.0, even as a character reference
+//! assert!(xml.contains(r"<title>Tom &amp; Jerry \x1b[1m</title>"));
+//!
+//! // SVG output and the HTML preview escape text the same way
+//! let windows = vec![WindowInfo {
+//!     position: WindowPosition { x: 0, y: 0, width: 100, height: 100 },
+//!     ..windows[0].clone()
+//! }];
+//! let svg = windows.format_with(OutputFormat::Svg);
+//! assert!(svg.contains(r"<title>Tom &amp; Jerry \x1b[1m (PID: 0)</title>"));
+//! assert!(svg.contains(r"[1] Tom &amp; Jerry \x1b[1m</text>"));
+//!
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Xml)
+//!     .xml(XmlOptions { layout: XmlLayout::Attributes })
+//!     .build()
+//!     .unwrap();
+//! let snapshot = Snapshot::parse(&windows.format_output(&config)).unwrap();
+//! assert_eq!(snapshot.windows[0].hwnd, 0x10318);
+//! ```
+//!
+//! ## TOML (requires the `toml` feature):
+//! ```
+//! # #[cfg(feature = "toml")]
+//! # {
+//! use window_enumerator_formatter::{OutputFormat, Snapshot, WindowInfo, WindowListFormat};
+//!
+//! let windows = vec![WindowInfo::builder().index(1).title("Editor".to_string()).build()];
+//! let toml = windows.format_with(OutputFormat::Toml);
+//! assert!(toml.starts_with("[[window]]\n"));
+//!
+//! let snapshot = Snapshot::parse(&toml).unwrap();
+//! assert_eq!(snapshot.windows[0].title, "Editor");
+//! # }
+//! ```
+//!
+//! ## MessagePack and CBOR (requires the `messagepack` or `cbor` feature):
+//! ```
+//! # #[cfg(feature = "cbor")]
+//! # {
+//! use window_enumerator_formatter::{
+//!     FormatConfig, OutputFormat, Snapshot, WindowInfo, WindowListFormat,
+//! };
+//!
+//! let windows = vec![WindowInfo::builder().index(1).title("Editor".to_string()).build()];
+//! let config = FormatConfig::builder().format(OutputFormat::Cbor).build().unwrap();
+//!
+//! // The String API returns binary output hex-encoded
+//! let bytes = windows.format_bytes(&config);
+//! assert_eq!(windows.format_output(&config).len(), bytes.len() * 2);
+//!
+//! let snapshot = Snapshot::parse_bytes(&bytes, OutputFormat::Cbor).unwrap();
+//! assert_eq!(snapshot.windows[0].title, "Editor");
+//!
+//! // A single window is encoded as one record, read back as a one-window list
+//! let bytes = windows[0].format_bytes(&config);
+//! let snapshot = Snapshot::parse_bytes(&bytes, OutputFormat::Cbor).unwrap();
+//! assert_eq!(snapshot.windows[0].title, "Editor");
+//! # }
+//! ```
+//!
+//! ## SQL:
+//! ```
+//! use window_enumerator_formatter::{
+//!     FormatConfig, OutputFormat, SqlDialect, SqlOptions, WindowInfo, WindowListFormat,
+//! };
+//!
+//! let windows = vec![WindowInfo::builder().index(1).title("Tom's editor".to_string()).build()];
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Sql)
+//!     .sql(SqlOptions {
+//!         table: "desktop".to_string(),
+//!         dialect: SqlDialect::Postgres,
+//!         ..Default::default()
+//!     })
+//!     .build()
+//!     .unwrap();
+//!
+//! let sql = windows.format_output(&config);
+//! assert!(sql.starts_with("CREATE TABLE \"desktop\" (\n    \"index\" BIGINT NOT NULL,"));
+//! assert!(sql.contains("VALUES\n    (1, 0, 0, 'Tom''s editor', "));
+//! ```
+//!
+//! ## Prometheus:
+//! ```
+//! use window_enumerator_formatter::{
+//!     FormatConfig, OutputFormat, PrometheusOptions, WindowInfo, WindowListFormat, WindowPosition,
+//! };
+//!
+//! let window = |process: &str| {
+//!     WindowInfo::builder()
+//!         .process_name(process.to_string())
+//!         .position(WindowPosition { x: 0, y: 0, width: 800, height: 600 })
+//!         .build()
+//! };
+//! let windows = vec![window("chrome.exe"), window("chrome.exe"), window("say \"hi\".exe")];
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Prometheus)
+//!     .prometheus(PrometheusOptions { prefix: "desktop_".to_string() })
+//!     .build()
+//!     .unwrap();
+//!
+//! let metrics = windows.format_output(&config);
+//! assert!(metrics.contains("# TYPE desktop_windows_total gauge\ndesktop_windows_total 3\n"));
+//! assert!(metrics.contains("desktop_window_count{process=\"chrome.exe\"} 2\n"));
+//! assert!(metrics.contains(r#"desktop_window_area_pixels{process="say \"hi\".exe"} 480000"#));
+//! ```
+//!
+//! ## Paths that are not valid Unicode:
+//! ```
+//! # #[cfg(unix)]
+//! # {
+//! use std::ffi::OsString;
+//! use std::os::unix::ffi::OsStringExt;
+//! use std::path::PathBuf;
+//! use window_enumerator_formatter::{
+//!     FormatConfig, OutputFormat, PathEncoding, Snapshot, WindowInfo, WindowListFormat,
+//! };
+//!
+//! let file = PathBuf::from(OsString::from_vec(b"/opt/caf\xe9/app".to_vec()));
+//! let windows = vec![WindowInfo::builder().process_file(file.clone()).build()];
+//!
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Json)
+//!     .path_encoding(PathEncoding::Escaped)
+//!     .build()
+//!     .unwrap();
+//! let json = windows.format_output(&config);
+//! assert!(json.contains(r#""process_file":{"escaped":"/opt/caf\\xe9/app"}"#));
+//! assert_eq!(Snapshot::parse(&json).unwrap().windows[0].process_file, file);
+//!
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Csv)
+//!     .path_encoding(PathEncoding::Escaped)
+//!     .build()
+//!     .unwrap();
+//! assert!(windows.format_output(&config).contains(r"/opt/caf\xe9/app"));
+//! # }
+//! ```
+//!
+//! ## Spreadsheet safety:
+//!
+//! Window titles come from arbitrary programs and web pages. CSV, TSV and Markdown
+//! output prefix text cells that a spreadsheet would evaluate as a formula with `'`.
+//! Numeric columns are left alone, so negative coordinates stay numbers.
+//! ```
+//! use window_enumerator_formatter::{
+//!     FormatConfig, OutputFormat, WindowInfo, WindowListFormat, WindowPosition,
+//! };
+//!
+//! let windows = vec![WindowInfo::builder()
+//!     .title("=HYPERLINK(\"http://evil.example\",\"x\")".to_string())
+//!     .class_name("@SUM(A1)".to_string())
+//!     .process_name("+cmd".to_string())
+//!     .position(WindowPosition { x: -1920, y: 0, width: 800, height: 600 })
+//!     .build()];
+//!
+//! let csv = windows.format_with(OutputFormat::Csv);
+//! let row = csv.lines().nth(1).unwrap();
+//! assert!(row.contains("\"'=HYPERLINK(\"\"http://evil.example\"\",\"\"x\"\")\""));
+//! assert!(row.contains(",'@SUM(A1),'+cmd,"));
+//! assert!(row.ends_with(",-1920,0,800,600"));
+//!
+//! let tsv = windows.format_with(OutputFormat::Tsv);
+//! assert!(tsv.contains("\t'+cmd\t"));
+//!
+//! let markdown = windows.format_with(OutputFormat::Markdown);
+//! assert!(markdown.contains("| '@SUM(A1) |"));
+//!
+//! // Tab- and carriage-return-led cells are neutralized too
+//! let windows = vec![WindowInfo::builder().title("\t=1+1".to_string()).build()];
+//! assert!(windows.format_with(OutputFormat::Csv).contains(",'\t=1+1,"));
+//!
+//! // Opt out when the consumer is not a spreadsheet
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Csv)
+//!     .escape_formulas(false)
+//!     .build()
+//!     .unwrap();
+//! assert!(windows.format_output(&config).contains(",\t=1+1,"));
+//! ```
+//!
+//! ## Colors and styles:
+//!
+//! Table, Simple and Detail output is colored when [`FormatConfig::color`] allows it:
+//! `Auto` colors only when stdout is a terminal and honors `NO_COLOR` and
+//! `CLICOLOR_FORCE`. Custom templates can style fields with `{field|style}`.
+//! ```
+//! use window_enumerator_formatter::{
+//!     ColorChoice, FormatConfig, OutputFormat, TemplateFormat, WindowInfo,
+//! };
+//!
+//! let window = WindowInfo::builder().pid(42).title("Editor".to_string()).build();
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Custom)
+//!     .template(TemplateFormat::Custom("{title|color:red} {pid|bold}".into()))
+//!     .color(ColorChoice::Always)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(window.format(&config), "\x1b[31mEditor\x1b[0m \x1b[1m42\x1b[0m");
+//!
+//! // Styles are dropped when color is off
+//! let config = FormatConfig { color: ColorChoice::Never, ..config };
+//! assert_eq!(window.format(&config), "Editor 42");
+//!
+//! // JSON and YAML can be syntax-highlighted, but never through the byte API
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Json)
+//!     .template(TemplateFormat::Fields(vec!["pid".into(), "title".into()]))
+//!     .color(ColorChoice::Always)
+//!     .highlight(true)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(
+//!     window.format(&config),
+//!     "{\x1b[34;1m\"pid\"\x1b[0m:\x1b[36m42\x1b[0m,\x1b[34;1m\"title\"\x1b[0m:\x1b[32m\"Editor\"\x1b[0m}"
+//! );
+//! assert_eq!(window.format_bytes(&config), br#"{"pid":42,"title":"Editor"}"#);
+//!
+//! let config = FormatConfig { format: OutputFormat::Yaml, ..config };
+//! assert_eq!(
+//!     window.format(&config),
+//!     "\x1b[34;1mpid\x1b[0m: \x1b[36m42\x1b[0m\n\x1b[34;1mtitle\x1b[0m: \x1b[32mEditor\x1b[0m\n"
+//! );
+//! ```
+//!
+//! ## Control characters:
+//!
+//! Human-readable formats strip ANSI escapes, control characters and bidi
+//! overrides from window strings, so a hostile title cannot clear the terminal
+//! or break table rows. Use [`ControlChars::Escape`] to see them instead.
+//! ```
+//! use window_enumerator_formatter::{ControlChars, FormatConfig, OutputFormat, WindowInfo};
+//!
+//! let window = WindowInfo::builder()
+//!     .title("\x1b[2Jevil\ntitle\u{202e}".to_string())
+//!     .build();
+//!
+//! assert_eq!(
+//!     window.format_with(OutputFormat::Simple),
+//!     "[0] evil title (PID: 0) @ (0,0)"
+//! );
+//!
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Simple)
+//!     .control_chars(ControlChars::Escape)
+//!     .max_title_length(None)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(
+//!     window.format(&config),
+//!     "[0] \\x1b[2Jevil\\ntitle\\u{202e} (PID: 0) @ (0,0)"
+//! );
+//! ```
+//!
+//! ## Redaction:
+//!
+//! A [`RedactionPolicy`] hides sensitive data in every format before a dump is shared.
+//! ```
+//! use window_enumerator_formatter::{
+//!     ColorChoice, FormatConfig, OutputFormat, RedactionPolicy, TemplateFormat, WindowInfo,
+//! };
+//! use std::path::PathBuf;
+//!
+//! let window = WindowInfo::builder()
+//!     .title("Invoice for ACME Corp".to_string())
+//!     .process_file(PathBuf::from(r"C:\Users\alice\AppData\app.exe"))
+//!     .build();
+//!
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Json)
+//!     .redaction(RedactionPolicy {
+//!         title_prefix: Some(7),
+//!         home_dirs: true,
+//!         drop: vec!["pid".to_string(), "class".to_string()],
+//!         ..Default::default()
+//!     })
+//!     .build()
+//!     .unwrap();
+//! let json = window.format(&config);
+//! assert!(json.contains(r#""title":"Invoice***""#));
+//! assert!(json.contains(r#""process_file":"C:\\Users\\<user>\\AppData\\app.exe""#));
+//! assert!(!json.contains("pid") && !json.contains("class_name"));
+//!
+//! // Other formats leave out dropped lines or show a placeholder, never fake values
+//! let policy = RedactionPolicy {
+//!     drop: vec!["pid".to_string(), "file".to_string()],
+//!     ..Default::default()
+//! };
+//! let config = FormatConfig::builder()
+//!     .format(OutputFormat::Detail)
+//!     .color(ColorChoice::Never)
+//!     .redaction(policy.clone())
+//!     .build()
+//!     .unwrap();
+//! let detail = window.format(&config);
+//! assert!(!detail.contains("PID:") && !detail.contains("File:"));
+//!
+//! let config = FormatConfig { format: OutputFormat::Simple, ..config };
+//! assert_eq!(
+//!     window.format(&config),
+//!     "[0] Invoice for ACME Corp (PID: <redacted>) @ (0,0)"
+//! );
+//! ```
+//!
+//! ## Loading configuration:
+//! ```
+//! use window_enumerator_formatter::{FormatConfig, OutputFormat};
+//!
+//! // Built-in presets: "compact", "audit", "wide-table"
+//! let config = FormatConfig::preset("wide-table").unwrap();
+//! assert_eq!(config.format, OutputFormat::Table);
+//! assert_eq!(config.max_title_length, None);
+//!
+//! // Settings are serde-serializable and can be kept in a file
+//! let path = std::env::temp_dir().join("wef-doc-config.yaml");
+//! std::fs::write(&path, "preset: compact\nmax_title_length: 20\n").unwrap();
+//! let config = FormatConfig::from_file(&path).unwrap();
+//! assert_eq!(config.format, OutputFormat::Simple);
+//! assert_eq!(config.max_title_length, Some(20));
+//!
+//! // Layer defaults < preset < file < `WEF_*` environment variables
+//! let config = FormatConfig::load(Some(&path)).unwrap();
+//! # let _ = config;
 //! ```
 //!
 //! ## With window-enumerator integration (requires feature):
@@ -193,15 +701,66 @@
 
 #![warn(missing_docs)]
 
+#[cfg(any(feature = "messagepack", feature = "cbor"))]
+mod binary;
+mod color;
+mod config;
+mod csv;
+mod encoding;
 mod error;
 mod formatter;
+mod highlight;
+mod html;
+mod layout;
+mod markdown;
+mod minimap;
 mod models;
+mod path_encoding;
+mod projection;
+mod prometheus;
+mod redact;
+mod sanitize;
+#[cfg(feature = "schemars")]
+mod schema;
+mod serialization;
+mod snapshot;
+mod sql;
+#[cfg(feature = "rusqlite")]
+mod sqlite;
+mod summary;
+mod svg;
+#[cfg(feature = "toml")]
+mod toml_format;
+mod xml;
 
+pub use color::ColorChoice;
+pub use config::ENV_PREFIX;
+pub use csv::{CsvDialect, LineTerminator, QuoteStyle};
+pub use encoding::OutputEncoding;
 pub use error::FormatError;
 pub use formatter::{
-    FormatConfig, OutputFormat, TemplateFormat, WindowFormatter, WindowListFormat,
+    FormatConfig, FormatConfigBuilder, OutputFormat, TemplateFormat, WindowFormatter,
+    WindowListFormat, MIN_TITLE_LENGTH, TEMPLATE_FIELDS,
 };
+pub use html::HtmlOptions;
+pub use layout::{Overflow, TableOptions, TableWidth};
+pub use markdown::{ColumnAlignment, MarkdownLayout, MarkdownOptions};
+pub use minimap::MinimapOptions;
 pub use models::{WindowInfo, WindowPosition};
+pub use path_encoding::PathEncoding;
+pub use prometheus::PrometheusOptions;
+pub use redact::RedactionPolicy;
+pub use sanitize::ControlChars;
+#[cfg(feature = "schemars")]
+pub use schema::{snapshot_schema, summary_schema, window_schema};
+pub use serialization::{HandleEncoding, KeyCase, PathStyle, SerializationOptions};
+pub use snapshot::{Snapshot, SCHEMA_VERSION};
+pub use sql::{SqlDialect, SqlOptions};
+#[cfg(feature = "rusqlite")]
+pub use sqlite::SNAPSHOT_TABLE;
+pub use summary::{ProcessSummary, WindowSummary};
+pub use svg::SvgOptions;
+pub use xml::{XmlLayout, XmlOptions};
 
 // 为 WindowInfo 实现格式化方法，消除循环依赖
 impl WindowInfo {
@@ -210,6 +769,29 @@ impl WindowInfo {
         WindowFormatter::format_window(self, config)
     }
 
+    /// Format this window according to the configuration, after checking its
+    /// path with [`WindowFormatter::check_paths`].
+    pub fn try_format(&self, config: &FormatConfig) -> Result<String, FormatError> {
+        WindowFormatter::try_format_window(self, config)
+    }
+
+    /// Format this window as bytes according to the configuration.
+    pub fn format_bytes(&self, config: &FormatConfig) -> Vec<u8> {
+        WindowFormatter::format_window_bytes(self, config)
+    }
+
+    /// Write this window as bytes to `writer`, after checking its path with
+    /// [`WindowFormatter::check_paths`].
+    pub fn write_bytes(
+        &self,
+        config: &FormatConfig,
+        writer: &mut dyn std::io::Write,
+    ) -> Result<(), FormatError> {
+        WindowFormatter::check_paths(std::slice::from_ref(self), config)?;
+        writer.write_all(&self.format_bytes(config))?;
+        Ok(())
+    }
+
     /// Format this window with a specific output format.
     pub fn format_with(&self, format: OutputFormat) -> String {
         let config = FormatConfig {
@@ -0,0 +1,268 @@
+//! GitHub-flavored Markdown output.
+
+use crate::formatter::{FormatConfig, WindowFormatter};
+use crate::models::WindowInfo;
+use crate::redact::REDACTED;
+use serde::{Deserialize, Serialize};
+
+/// Alignment of a Markdown table column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColumnAlignment {
+    /// No alignment marker (`---`), left to the renderer.
+    #[default]
+    Default,
+    /// Left aligned (`:--`).
+    Left,
+    /// Centered (`:-:`).
+    Center,
+    /// Right aligned (`--:`).
+    Right,
+}
+
+impl ColumnAlignment {
+    fn marker(self) -> &'static str {
+        match self {
+            ColumnAlignment::Default => "---",
+            ColumnAlignment::Left => ":--",
+            ColumnAlignment::Center => ":-:",
+            ColumnAlignment::Right => "--:",
+        }
+    }
+}
+
+/// Layout of Markdown output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MarkdownLayout {
+    /// One table row per window.
+    #[default]
+    Table,
+    /// A heading per window followed by a definition list of its fields,
+    /// the Markdown counterpart of the Detail format.
+    DefinitionList,
+}
+
+/// Options for [`OutputFormat::Markdown`](crate::OutputFormat::Markdown).
+///
+/// ```
+/// use window_enumerator_formatter::{
+///     FormatConfig, MarkdownLayout, MarkdownOptions, OutputFormat, WindowInfo, WindowListFormat,
+///     WindowPosition,
+/// };
+///
+/// let windows = vec![WindowInfo::builder()
+///     .index(1)
+///     .hwnd(0x10318)
+///     .pid(4242)
+///     .title("Docs | README".to_string())
+///     .class_name("Chrome_WidgetWin_1".to_string())
+///     .process_name("chrome.exe".to_string())
+///     .process_file("chrome.exe".into())
+///     .position(WindowPosition { x: 0, y: 0, width: 1200, height: 800 })
+///     .build()];
+///
+/// // Numbers are right aligned and text left aligned by default
+/// assert_eq!(
+///     windows.format_with(OutputFormat::Markdown),
+///     "| Index | Handle | PID | Title | Class | Process | File | Position | Size |\n\
+///      | --: | --: | --: | :-- | :-- | :-- | :-- | --: | --: |\n\
+///      | 1 | 0x10318 | 4242 | Docs \\| README | Chrome_WidgetWin_1 | chrome.exe | chrome.exe | 0,0 | 1200x800 |\n"
+/// );
+///
+/// let config = FormatConfig::builder()
+///     .format(OutputFormat::Markdown)
+///     .markdown(MarkdownOptions {
+///         layout: MarkdownLayout::DefinitionList,
+///         ..Default::default()
+///     })
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     windows.format_output(&config),
+///     "### [1] Docs \\| README\n\
+///      \nHandle\n: 0x10318\n\
+///      \nPID\n: 4242\n\
+///      \nTitle\n: Docs \\| README\n\
+///      \nClass\n: Chrome_WidgetWin_1\n\
+///      \nProcess\n: chrome.exe\n\
+///      \nFile\n: chrome.exe\n\
+///      \nPosition\n: (0, 0)\n\
+///      \nSize\n: 1200x800\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkdownOptions {
+    /// Table or definition list layout.
+    pub layout: MarkdownLayout,
+    /// Alignment of text columns (title, class, process, file).
+    pub text_alignment: ColumnAlignment,
+    /// Alignment of numeric columns (index, handle, PID, position, size).
+    pub number_alignment: ColumnAlignment,
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        Self {
+            layout: MarkdownLayout::Table,
+            text_alignment: ColumnAlignment::Left,
+            number_alignment: ColumnAlignment::Right,
+        }
+    }
+}
+
+// Column headers, and whether each column holds numbers
+const COLUMNS: [(&str, bool); 9] = [
+    ("Index", true),
+    ("Handle", true),
+    ("PID", true),
+    ("Title", false),
+    ("Class", false),
+    ("Process", false),
+    ("File", false),
+    ("Position", true),
+    ("Size", true),
+];
+
+impl WindowFormatter {
+    // Markdown format - list
+    pub(crate) fn format_markdown(windows: &[WindowInfo], config: &FormatConfig) -> String {
+        match config.markdown.layout {
+            MarkdownLayout::Table => Self::format_markdown_table(windows, config),
+            MarkdownLayout::DefinitionList => windows
+                .iter()
+                .map(|w| Self::format_markdown_definitions(w, config))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+
+    // Markdown table; the header is always emitted because GFM tables require one
+    fn format_markdown_table(windows: &[WindowInfo], config: &FormatConfig) -> String {
+        let options = &config.markdown;
+        let headers: Vec<&str> = COLUMNS.iter().map(|(name, _)| *name).collect();
+        let markers: Vec<&str> = COLUMNS
+            .iter()
+            .map(|(_, numeric)| {
+                if *numeric {
+                    options.number_alignment.marker()
+                } else {
+                    options.text_alignment.marker()
+                }
+            })
+            .collect();
+
+        let mut output = String::new();
+        output.push_str(&format!("| {} |\n", headers.join(" | ")));
+        output.push_str(&format!("| {} |\n", markers.join(" | ")));
+
+        let policy = &config.redaction;
+        for window in windows {
+            let text = |field: &str, value: &str| {
+                if policy.drops(field) {
+                    escape_markdown(REDACTED)
+                } else {
+                    escape_markdown(&Self::spreadsheet_text(value, config))
+                }
+            };
+            let p = &window.position;
+            let row = [
+                escape_markdown(&policy.show("index", window.index)),
+                escape_markdown(&policy.show("hwnd", format_args!("0x{:x}", window.hwnd))),
+                escape_markdown(&policy.show("pid", window.pid)),
+                text(
+                    "title",
+                    &Self::truncate_title(&window.title, config.max_title_length),
+                ),
+                text("class", &window.class_name),
+                text("process", &window.process_name),
+                text("file", &window.process_file.to_string_lossy()),
+                escape_markdown(&format!(
+                    "{},{}",
+                    policy.show("x", p.x),
+                    policy.show("y", p.y)
+                )),
+                escape_markdown(&format!(
+                    "{}x{}",
+                    policy.show("width", p.width),
+                    policy.show("height", p.height)
+                )),
+            ];
+            output.push_str(&format!("| {} |\n", row.join(" | ")));
+        }
+
+        output
+    }
+
+    // Markdown definition list - single window; dropped fields are left out
+    fn format_markdown_definitions(window: &WindowInfo, config: &FormatConfig) -> String {
+        let policy = &config.redaction;
+        let p = &window.position;
+        let mut fields: Vec<(&str, String)> = [
+            ("hwnd", "Handle", format!("0x{:x}", window.hwnd)),
+            ("pid", "PID", window.pid.to_string()),
+            ("title", "Title", escape_markdown(&window.title)),
+            ("class", "Class", escape_markdown(&window.class_name)),
+            ("process", "Process", escape_markdown(&window.process_name)),
+            (
+                "file",
+                "File",
+                escape_markdown(&window.process_file.to_string_lossy()),
+            ),
+        ]
+        .into_iter()
+        .filter(|(field, ..)| !policy.drops(field))
+        .map(|(_, term, definition)| (term, definition))
+        .collect();
+
+        // A coordinate pair is left out only if both halves are dropped
+        let pair = |first: &str, second: &str| !(policy.drops(first) && policy.drops(second));
+        if pair("x", "y") {
+            fields.push((
+                "Position",
+                escape_markdown(&format!(
+                    "({}, {})",
+                    policy.show("x", p.x),
+                    policy.show("y", p.y)
+                )),
+            ));
+        }
+        if pair("width", "height") {
+            fields.push((
+                "Size",
+                escape_markdown(&format!(
+                    "{}x{}",
+                    policy.show("width", p.width),
+                    policy.show("height", p.height)
+                )),
+            ));
+        }
+
+        let mut output = format!(
+            "### [{}] {}\n",
+            escape_markdown(&policy.show("index", window.index)),
+            escape_markdown(&Self::display_title(window, config))
+        );
+        for (term, definition) in fields {
+            output.push_str(&format!("\n{}\n: {}\n", term, definition));
+        }
+        output
+    }
+}
+
+// Escape characters that would break table cells or start inline markup or HTML
+fn escape_markdown(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for c in field.chars() {
+        match c {
+            '\\' | '|' | '`' | '<' | '>' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\r' | '\n' => escaped.push(' '),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,207 @@
+//! Character-grid minimap of window geometry.
+
+use crate::formatter::{FormatConfig, WindowFormatter};
+use crate::models::{WindowInfo, WindowPosition};
+use serde::{Deserialize, Serialize};
+
+/// Options for [`OutputFormat::Minimap`](crate::OutputFormat::Minimap).
+///
+/// ```
+/// use window_enumerator_formatter::{
+///     FormatConfig, MinimapOptions, OutputFormat, WindowInfo, WindowListFormat, WindowPosition,
+/// };
+///
+/// let windows = vec![
+///     WindowInfo::builder()
+///         .index(1)
+///         .title("Browser".to_string())
+///         .process_name("chrome.exe".to_string())
+///         .position(WindowPosition { x: 0, y: 0, width: 1200, height: 800 })
+///         .build(),
+///     WindowInfo::builder()
+///         .index(2)
+///         .title("Terminal".to_string())
+///         .process_name("wt.exe".to_string())
+///         .position(WindowPosition { x: 1300, y: 200, width: 500, height: 600 })
+///         .build(),
+/// ];
+///
+/// let config = FormatConfig::builder()
+///     .format(OutputFormat::Minimap)
+///     .minimap(MinimapOptions { width: 32, height: 9, unicode: false })
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     windows.format_output(&config),
+///     "\
+/// +1------------------+
+/// |                   |
+/// |                   |  +2------+
+/// |                   |  |       |
+/// |                   |  |       |
+/// |                   |  |       |
+/// |                   |  |       |
+/// |                   |  |       |
+/// +-------------------+  +-------+
+///
+///    1  Browser [chrome.exe]
+///    2  Terminal [wt.exe]
+/// "
+/// );
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MinimapOptions {
+    /// Grid width in characters.
+    pub width: usize,
+    /// Grid height in characters.
+    pub height: usize,
+    /// Draw with Unicode box characters instead of ASCII `+`, `-` and `|`.
+    pub unicode: bool,
+}
+
+impl Default for MinimapOptions {
+    fn default() -> Self {
+        Self {
+            width: 80,
+            height: 24,
+            unicode: true,
+        }
+    }
+}
+
+/// Smallest grid dimension that can hold a box.
+pub(crate) const MIN_MINIMAP_SIZE: usize = 2;
+
+// Corner, horizontal and vertical characters: top-left, top-right, bottom-left, bottom-right, -, |
+const UNICODE_BOX: [char; 6] = ['┌', '┐', '└', '┘', '─', '│'];
+const ASCII_BOX: [char; 6] = ['+', '+', '+', '+', '-', '|'];
+
+impl WindowFormatter {
+    // Minimap format - list
+    pub(crate) fn format_minimap(windows: &[WindowInfo], config: &FormatConfig) -> String {
+        let options = &config.minimap;
+        let policy = &config.redaction;
+        if policy.drops_geometry() {
+            return format!(
+                "Window positions are redacted\n\n{}",
+                Self::minimap_legend(windows, config)
+            );
+        }
+        let Some(bounds) = WindowPosition::bounding_box(windows.iter().map(|w| &w.position)) else {
+            return "No visible windows".to_string();
+        };
+
+        // Configs built without validation may be smaller than a box
+        let cols = options.width.max(MIN_MINIMAP_SIZE);
+        let rows = options.height.max(MIN_MINIMAP_SIZE);
+        let chars = if options.unicode {
+            UNICODE_BOX
+        } else {
+            ASCII_BOX
+        };
+        let mut grid = vec![vec![' '; cols]; rows];
+
+        // Draw back to front so the first window in the list ends up on top
+        for window in windows.iter().rev().filter(|w| w.position.area() > 0) {
+            let p = &window.position;
+            let (left, right) = scale_span(p.x, p.width, bounds.x, bounds.width, cols);
+            let (top, bottom) = scale_span(p.y, p.height, bounds.y, bounds.height, rows);
+
+            for row in &mut grid[top..=bottom] {
+                row[left..=right].fill(' ');
+                row[left] = chars[5];
+                row[right] = chars[5];
+            }
+            grid[top][left + 1..right].fill(chars[4]);
+            grid[bottom][left + 1..right].fill(chars[4]);
+            grid[top][left] = chars[0];
+            grid[top][right] = chars[1];
+            grid[bottom][left] = chars[2];
+            grid[bottom][right] = chars[3];
+
+            for (offset, label) in policy.show("index", window.index).chars().enumerate() {
+                let col = left + 1 + offset;
+                if col >= right {
+                    break;
+                }
+                grid[top][col] = label;
+            }
+        }
+
+        let mut output: String = grid
+            .iter()
+            .map(|row| row.iter().collect::<String>().trim_end().to_string() + "\n")
+            .collect();
+
+        output.push('\n');
+        output.push_str(&Self::minimap_legend(windows, config));
+        output
+    }
+
+    // One line per window: index, title, process, and whether it has no area
+    fn minimap_legend(windows: &[WindowInfo], config: &FormatConfig) -> String {
+        let policy = &config.redaction;
+        let mut output = String::new();
+        for window in windows {
+            let hidden = if Self::is_zero_size(window, config) {
+                " (zero size)"
+            } else {
+                ""
+            };
+            output.push_str(&format!(
+                "{:>4}  {} [{}]{}\n",
+                policy.show("index", window.index),
+                Self::display_title(window, config),
+                policy.show("process", &window.process_name),
+                hidden
+            ));
+        }
+        output
+    }
+}
+
+// Map a screen span onto cells `first..=last`, keeping at least two cells so a box fits
+fn scale_span(start: i32, length: i32, origin: i32, extent: i32, cells: usize) -> (usize, usize) {
+    let to_cell = |offset: i64| (offset * cells as i64 / extent.max(1) as i64) as usize;
+    let offset = start as i64 - origin as i64;
+    let first = to_cell(offset).min(cells - MIN_MINIMAP_SIZE);
+    let last = to_cell(offset + length as i64)
+        .saturating_sub(1)
+        .clamp(first + 1, cells - 1);
+    (first, last)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        FormatConfig, MinimapOptions, OutputFormat, WindowInfo, WindowListFormat, WindowPosition,
+    };
+
+    #[test]
+    fn unvalidated_sizes_are_clamped() {
+        let windows = vec![WindowInfo::builder()
+            .position(WindowPosition {
+                x: 0,
+                y: 0,
+                width: 800,
+                height: 600,
+            })
+            .build()];
+        let config = FormatConfig {
+            format: OutputFormat::Minimap,
+            minimap: MinimapOptions {
+                width: 0,
+                height: 1,
+                unicode: false,
+            },
+            ..Default::default()
+        };
+        assert!(FormatConfig::builder()
+            .format(OutputFormat::Minimap)
+            .minimap(config.minimap.clone())
+            .build()
+            .is_err());
+        assert!(windows.format_output(&config).starts_with("++\n++\n"));
+    }
+}
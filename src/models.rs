@@ -1,8 +1,10 @@
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize};
 use std::path::PathBuf;
 
 /// Window position and size information.
-#[derive(Debug, Clone, Serialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(default)]
 pub struct WindowPosition {
     /// X coordinate of the window.
     pub x: i32,
@@ -14,10 +16,45 @@ pub struct WindowPosition {
     pub height: i32,
 }
 
+impl WindowPosition {
+    /// Area in pixels; negative sizes count as zero.
+    pub fn area(&self) -> u64 {
+        self.width.max(0) as u64 * self.height.max(0) as u64
+    }
+
+    /// Smallest rectangle containing every position with a non-zero area.
+    pub fn bounding_box<'a>(
+        positions: impl IntoIterator<Item = &'a WindowPosition>,
+    ) -> Option<Self> {
+        let mut bounds: Option<(i64, i64, i64, i64)> = None;
+        for p in positions.into_iter().filter(|p| p.area() > 0) {
+            let (left, top) = (p.x as i64, p.y as i64);
+            let (right, bottom) = (left + p.width as i64, top + p.height as i64);
+            bounds = Some(match bounds {
+                Some((l, t, r, b)) => (l.min(left), t.min(top), r.max(right), b.max(bottom)),
+                None => (left, top, right, bottom),
+            });
+        }
+        bounds.map(|(left, top, right, bottom)| Self {
+            x: left as i32,
+            y: top as i32,
+            width: (right - left).min(i32::MAX as i64) as i32,
+            height: (bottom - top).min(i32::MAX as i64) as i32,
+        })
+    }
+}
+
 /// Information about a window.
-#[derive(Debug, Clone, Serialize)]
+///
+/// Deserializing fills missing fields with defaults, accepts `hwnd` as a
+/// number or a `0x..` string, and restores `process_file` from the escaped and
+/// base64 forms written under [`PathEncoding`](crate::PathEncoding).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(default)]
 pub struct WindowInfo {
     /// Window handle.
+    #[serde(deserialize_with = "deserialize_handle")]
     pub hwnd: isize,
     /// Process ID.
     pub pid: u32,
@@ -28,6 +65,11 @@ pub struct WindowInfo {
     /// Process name.
     pub process_name: String,
     /// Process file path.
+    #[serde(deserialize_with = "crate::path_encoding::deserialize_path")]
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(schema_with = "crate::path_encoding::path_schema")
+    )]
     pub process_file: PathBuf,
     /// Index in the enumeration.
     pub index: usize,
@@ -35,6 +77,28 @@ pub struct WindowInfo {
     pub position: WindowPosition,
 }
 
+fn deserialize_handle<'de, D: Deserializer<'de>>(deserializer: D) -> Result<isize, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Handle {
+        Number(isize),
+        Text(String),
+    }
+
+    match Handle::deserialize(deserializer)? {
+        Handle::Number(hwnd) => Ok(hwnd),
+        Handle::Text(text) => {
+            // Hex handles are written as two's complement, so `-2` is `0xfff...fe`
+            let parsed = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+                Some(hex) => u64::from_str_radix(hex, 16).map(|hwnd| hwnd as isize),
+                None => text.parse(),
+            };
+            parsed
+                .map_err(|_| serde::de::Error::custom(format!("invalid window handle `{}`", text)))
+        }
+    }
+}
+
 impl WindowInfo {
     /// Create a new WindowInfo instance using builder pattern.
     pub fn builder() -> WindowInfoBuilder {
@@ -0,0 +1,306 @@
+//! Encoding of process file paths that are not valid Unicode.
+
+use crate::models::WindowInfo;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+/// How process file paths that are not valid Unicode are written.
+///
+/// Valid paths are written unchanged under every policy. Structured formats
+/// (JSON, YAML, NDJSON, TOML, MessagePack, CBOR) write the escaped and base64
+/// forms as `{"escaped": ".."}` and `{"base64": ".."}`, which
+/// [`Snapshot::parse`](crate::Snapshot::parse) turns back into the original path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PathEncoding {
+    /// Replace invalid sequences with U+FFFD.
+    #[default]
+    Lossy,
+    /// Write invalid bytes as `\xNN`, or unpaired UTF-16 surrogates on Windows
+    /// as `\u{d800}`. Structured formats also double backslashes, so the
+    /// escapes cannot be confused with path separators.
+    Escaped,
+    /// Write the bytes of the OS string (UTF-16LE code units on Windows) in
+    /// base64, prefixed with `base64:` in text formats.
+    Base64,
+    /// Refuse such paths: [`WindowListFormat::try_format_output`](crate::WindowListFormat::try_format_output),
+    /// [`WindowInfo::try_format`], [`WindowFormatter::check_paths`](crate::WindowFormatter::check_paths)
+    /// and the `write_bytes` methods fail with
+    /// [`FormatError::InvalidPath`](crate::FormatError::InvalidPath). Only the
+    /// infallible `String` and byte APIs, which cannot report the error, fall
+    /// back to [`PathEncoding::Lossy`].
+    Error,
+}
+
+impl PathEncoding {
+    /// The path as text for text formats.
+    pub(crate) fn display(self, path: &Path) -> Cow<'_, str> {
+        match (path.to_str(), self) {
+            (Some(text), _) => Cow::Borrowed(text),
+            (None, PathEncoding::Escaped) => Cow::Owned(escape(path, false)),
+            (None, PathEncoding::Base64) => {
+                Cow::Owned(format!("base64:{}", encode_base64(&os_bytes(path))))
+            }
+            (None, PathEncoding::Lossy | PathEncoding::Error) => path.to_string_lossy(),
+        }
+    }
+
+    /// The path as a value for structured formats.
+    pub(crate) fn encode(self, path: &Path) -> EncodedPath<'_> {
+        match (path.to_str(), self) {
+            (Some(text), _) => EncodedPath::Text(Cow::Borrowed(text)),
+            (None, PathEncoding::Escaped) => EncodedPath::Escaped(escape(path, true)),
+            (None, PathEncoding::Base64) => EncodedPath::Base64(encode_base64(&os_bytes(path))),
+            (None, PathEncoding::Lossy | PathEncoding::Error) => {
+                EncodedPath::Text(path.to_string_lossy())
+            }
+        }
+    }
+}
+
+/// A path as written by structured formats.
+pub(crate) enum EncodedPath<'a> {
+    Text(Cow<'a, str>),
+    Escaped(String),
+    Base64(String),
+}
+
+impl Serialize for EncodedPath<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (key, value) = match self {
+            EncodedPath::Text(text) => return serializer.serialize_str(text),
+            EncodedPath::Escaped(escaped) => ("escaped", escaped),
+            EncodedPath::Base64(base64) => ("base64", base64),
+        };
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(key, value)?;
+        map.end()
+    }
+}
+
+impl WindowInfo {
+    // Copy of the window with its path encoded for text formats, borrowed when the path is valid
+    pub(crate) fn with_path_encoding(&self, encoding: PathEncoding) -> Cow<'_, WindowInfo> {
+        match encoding.display(&self.process_file) {
+            Cow::Borrowed(_) => Cow::Borrowed(self),
+            Cow::Owned(file) => Cow::Owned(WindowInfo {
+                process_file: PathBuf::from(file),
+                ..self.clone()
+            }),
+        }
+    }
+}
+
+/// Deserialize a path written as a string, `{"escaped": ..}` or `{"base64": ..}`.
+pub(crate) fn deserialize_path<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<PathBuf, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StoredPath {
+        Text(PathBuf),
+        Escaped { escaped: String },
+        Base64 { base64: String },
+    }
+
+    match StoredPath::deserialize(deserializer)? {
+        StoredPath::Text(path) => Ok(path),
+        StoredPath::Escaped { escaped } => unescape(&escaped)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid escaped path `{}`", escaped))),
+        StoredPath::Base64 { base64 } => decode_base64(&base64)
+            .and_then(from_os_bytes)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid base64 path `{}`", base64))),
+    }
+}
+
+/// JSON Schema of a path accepted by [`deserialize_path`].
+#[cfg(feature = "schemars")]
+pub(crate) fn path_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::json_schema!({
+        "oneOf": [
+            { "type": "string" },
+            {
+                "type": "object",
+                "properties": { "escaped": { "type": "string" } },
+                "required": ["escaped"],
+                "additionalProperties": false
+            },
+            {
+                "type": "object",
+                "properties": { "base64": { "type": "string" } },
+                "required": ["base64"],
+                "additionalProperties": false
+            }
+        ]
+    })
+}
+
+// Bytes of the OS string; UTF-16LE code units on Windows
+#[cfg(unix)]
+fn os_bytes(path: &Path) -> Cow<'_, [u8]> {
+    use std::os::unix::ffi::OsStrExt;
+    Cow::Borrowed(path.as_os_str().as_bytes())
+}
+
+#[cfg(windows)]
+fn os_bytes(path: &Path) -> Cow<'_, [u8]> {
+    use std::os::windows::ffi::OsStrExt;
+    Cow::Owned(
+        path.as_os_str()
+            .encode_wide()
+            .flat_map(u16::to_le_bytes)
+            .collect(),
+    )
+}
+
+#[cfg(not(any(unix, windows)))]
+fn os_bytes(path: &Path) -> Cow<'_, [u8]> {
+    Cow::Owned(path.to_string_lossy().into_owned().into_bytes())
+}
+
+#[cfg(unix)]
+fn from_os_bytes(bytes: Vec<u8>) -> Option<PathBuf> {
+    use std::os::unix::ffi::OsStringExt;
+    Some(PathBuf::from(std::ffi::OsString::from_vec(bytes)))
+}
+
+#[cfg(windows)]
+fn from_os_bytes(bytes: Vec<u8>) -> Option<PathBuf> {
+    use std::os::windows::ffi::OsStringExt;
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    Some(PathBuf::from(std::ffi::OsString::from_wide(&units)))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn from_os_bytes(bytes: Vec<u8>) -> Option<PathBuf> {
+    String::from_utf8(bytes).ok().map(PathBuf::from)
+}
+
+// Append a character in the representation of `os_bytes`
+fn push_char(bytes: &mut Vec<u8>, c: char) {
+    if cfg!(windows) {
+        bytes.extend(
+            c.encode_utf16(&mut [0; 2])
+                .iter()
+                .flat_map(|u| u.to_le_bytes()),
+        );
+    } else {
+        bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+    }
+}
+
+// Valid text as is and invalid sequences as escapes, doubling backslashes if `reversible`
+fn escape(path: &Path, reversible: bool) -> String {
+    let mut output = String::new();
+    let push_text = |output: &mut String, text: &str| {
+        if reversible {
+            output.push_str(&text.replace('\\', "\\\\"));
+        } else {
+            output.push_str(text);
+        }
+    };
+
+    #[cfg(unix)]
+    for chunk in os_bytes(path).utf8_chunks() {
+        push_text(&mut output, chunk.valid());
+        for byte in chunk.invalid() {
+            output.push_str(&format!("\\x{:02x}", byte));
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        for unit in char::decode_utf16(path.as_os_str().encode_wide()) {
+            match unit {
+                Ok(c) => push_text(&mut output, c.encode_utf8(&mut [0; 4])),
+                Err(error) => output.push_str(&format!("\\u{{{:x}}}", error.unpaired_surrogate())),
+            }
+        }
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    push_text(&mut output, &path.to_string_lossy());
+
+    output
+}
+
+// Reverse `escape` with doubled backslashes
+fn unescape(text: &str) -> Option<PathBuf> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            push_char(&mut bytes, c);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => push_char(&mut bytes, '\\'),
+            'x' if cfg!(unix) => {
+                let hex: String = chars.by_ref().take(2).collect();
+                if hex.len() != 2 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return None;
+                }
+                bytes.push(u8::from_str_radix(&hex, 16).ok()?);
+            }
+            'u' if cfg!(windows) => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+                let hex: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                if hex.is_empty() || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return None;
+                }
+                bytes.extend(u16::from_str_radix(&hex, 16).ok()?.to_le_bytes());
+            }
+            _ => return None,
+        }
+    }
+    from_os_bytes(bytes)
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// Standard base64 with padding
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+            group | (byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(BASE64_ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=');
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let (mut group, mut bits) = (0u32, 0);
+    for c in text.bytes() {
+        let value = BASE64_ALPHABET.iter().position(|&a| a == c)? as u32;
+        group = group << 6 | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((group >> bits) as u8);
+            group &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
@@ -0,0 +1,288 @@
+//! Field selection for JSON and YAML output.
+
+use crate::error::FormatError;
+use crate::formatter::{FormatConfig, TemplateFormat};
+use crate::models::WindowInfo;
+use crate::path_encoding::{EncodedPath, PathEncoding};
+use crate::serialization::{HandleEncoding, KeyCase, PathStyle, SerializationOptions};
+use crate::snapshot::Envelope;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+
+/// A field of a serialized window, stored or computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Hwnd,
+    Pid,
+    Title,
+    ClassName,
+    ProcessName,
+    ProcessFile,
+    Index,
+    X,
+    Y,
+    Width,
+    Height,
+    Area,
+    Right,
+    Bottom,
+}
+
+/// Fields serialized when none are selected, in `WindowInfo` order.
+const ALL_FIELDS: [Field; 11] = [
+    Field::Hwnd,
+    Field::Pid,
+    Field::Title,
+    Field::ClassName,
+    Field::ProcessName,
+    Field::ProcessFile,
+    Field::Index,
+    Field::X,
+    Field::Y,
+    Field::Width,
+    Field::Height,
+];
+
+impl Field {
+    // Resolve a template field name, serialized key, dotted path or computed field
+    fn parse(name: &str) -> Option<&'static [Field]> {
+        let fields: &[Field] = match name.to_lowercase().as_str() {
+            "hwnd" => &[Field::Hwnd],
+            "pid" => &[Field::Pid],
+            "title" => &[Field::Title],
+            "class" | "class_name" => &[Field::ClassName],
+            "process" | "process_name" => &[Field::ProcessName],
+            "file" | "process_file" => &[Field::ProcessFile],
+            "index" => &[Field::Index],
+            "position" => &[Field::X, Field::Y, Field::Width, Field::Height],
+            "x" | "position.x" => &[Field::X],
+            "y" | "position.y" => &[Field::Y],
+            "width" | "position.width" => &[Field::Width],
+            "height" | "position.height" => &[Field::Height],
+            "area" | "position.area" => &[Field::Area],
+            "right" | "position.right" => &[Field::Right],
+            "bottom" | "position.bottom" => &[Field::Bottom],
+            _ => return None,
+        };
+        Some(fields)
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Field::Hwnd => "hwnd",
+            Field::Pid => "pid",
+            Field::Title => "title",
+            Field::ClassName => "class_name",
+            Field::ProcessName => "process_name",
+            Field::ProcessFile => "process_file",
+            Field::Index => "index",
+            Field::X => "x",
+            Field::Y => "y",
+            Field::Width => "width",
+            Field::Height => "height",
+            Field::Area => "area",
+            Field::Right => "right",
+            Field::Bottom => "bottom",
+        }
+    }
+
+    // Template fields the value reveals, so dropping `x` also hides `right`
+    fn sources(self) -> &'static [&'static str] {
+        match self {
+            Field::Hwnd => &["hwnd"],
+            Field::Pid => &["pid"],
+            Field::Title => &["title"],
+            Field::ClassName => &["class"],
+            Field::ProcessName => &["process"],
+            Field::ProcessFile => &["file"],
+            Field::Index => &["index"],
+            Field::X => &["x"],
+            Field::Y => &["y"],
+            Field::Width => &["width"],
+            Field::Height => &["height"],
+            Field::Area => &["width", "height"],
+            Field::Right => &["x", "width"],
+            Field::Bottom => &["y", "height"],
+        }
+    }
+
+    // Whether the field is nested under `position` unless flattened
+    fn in_position(self) -> bool {
+        matches!(self, Field::X | Field::Y | Field::Width | Field::Height)
+    }
+
+    fn serialize_entry<M: SerializeMap>(
+        self,
+        map: &mut M,
+        window: &WindowInfo,
+        projection: &Projection,
+    ) -> Result<(), M::Error> {
+        let (options, paths) = (projection.options, projection.paths);
+        let p = &window.position;
+        let key = &*options.key_case.apply(self.key());
+        match self {
+            Field::Hwnd if options.handle == HandleEncoding::Hex => {
+                map.serialize_entry(key, &format!("0x{:x}", window.hwnd))
+            }
+            Field::ProcessFile => match paths.encode(&window.process_file) {
+                EncodedPath::Text(file) if options.path_style == PathStyle::Forward => {
+                    map.serialize_entry(key, &file.replace('\\', "/"))
+                }
+                file => map.serialize_entry(key, &file),
+            },
+            Field::Hwnd => map.serialize_entry(key, &window.hwnd),
+            Field::Pid => map.serialize_entry(key, &window.pid),
+            Field::Title => map.serialize_entry(key, &window.title),
+            Field::ClassName => map.serialize_entry(key, &window.class_name),
+            Field::ProcessName => map.serialize_entry(key, &window.process_name),
+            Field::Index => map.serialize_entry(key, &window.index),
+            Field::X => map.serialize_entry(key, &p.x),
+            Field::Y => map.serialize_entry(key, &p.y),
+            Field::Width => map.serialize_entry(key, &p.width),
+            Field::Height => map.serialize_entry(key, &p.height),
+            Field::Area => map.serialize_entry(key, &p.area()),
+            Field::Right => map.serialize_entry(key, &(p.x as i64 + p.width as i64)),
+            Field::Bottom => map.serialize_entry(key, &(p.y as i64 + p.height as i64)),
+        }
+    }
+}
+
+/// Check a field list used to select JSON and YAML fields.
+pub(crate) fn validate_fields(names: &[String]) -> Result<(), FormatError> {
+    if names.is_empty() {
+        return Err(FormatError::TemplateError {
+            message: "template field list is empty".to_string(),
+        });
+    }
+    match names.iter().find(|name| Field::parse(name).is_none()) {
+        Some(name) => Err(FormatError::InvalidField {
+            field: name.clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// The fields serialized for each window, and how `position` is laid out.
+pub(crate) struct Projection {
+    fields: Vec<Field>,
+    flatten: bool,
+    options: SerializationOptions,
+    paths: PathEncoding,
+}
+
+impl Projection {
+    pub(crate) fn new(config: &FormatConfig) -> Self {
+        let selected: Vec<Field> = match &config.template {
+            Some(TemplateFormat::Fields(names) | TemplateFormat::KeyValue(names)) => names
+                .iter()
+                .filter_map(|name| Field::parse(name))
+                .flatten()
+                .copied()
+                .collect(),
+            _ => ALL_FIELDS.to_vec(),
+        };
+
+        let mut fields = Vec::with_capacity(selected.len());
+        for field in selected {
+            let dropped = field
+                .sources()
+                .iter()
+                .any(|source| config.redaction.drops(source));
+            if !dropped && !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+
+        Self {
+            fields,
+            flatten: config.flatten_position,
+            options: config.serialization,
+            paths: config.path_encoding,
+        }
+    }
+
+    /// Pair a window with this projection for serialization.
+    pub(crate) fn record<'a>(&'a self, window: &'a WindowInfo) -> Record<'a> {
+        Record {
+            window,
+            projection: self,
+        }
+    }
+}
+
+/// A window serialized with the fields of a [`Projection`].
+#[derive(Clone, Copy)]
+pub(crate) struct Record<'a> {
+    window: &'a WindowInfo,
+    projection: &'a Projection,
+}
+
+impl Serialize for Record<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let projection = self.projection;
+        let mut map = serializer.serialize_map(None)?;
+        let mut position_written = false;
+
+        for field in &projection.fields {
+            if !projection.flatten && field.in_position() {
+                // Position fields are grouped where the first one was selected
+                if !position_written {
+                    let key = projection.options.key_case.apply("position");
+                    map.serialize_entry(&key, &PositionRecord(*self))?;
+                    position_written = true;
+                }
+                continue;
+            }
+            field.serialize_entry(&mut map, self.window, projection)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Clone, Copy)]
+struct PositionRecord<'a>(Record<'a>);
+
+impl Serialize for PositionRecord<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Record { window, projection } = self.0;
+        let mut map = serializer.serialize_map(None)?;
+        for field in projection.fields.iter().filter(|field| field.in_position()) {
+            field.serialize_entry(&mut map, window, projection)?;
+        }
+        map.end()
+    }
+}
+
+/// A list of records, bare, wrapped as `{ total, offset, items }`, or in a
+/// snapshot envelope.
+pub(crate) enum Items<'a> {
+    Bare(&'a [Record<'a>]),
+    Page {
+        total: usize,
+        offset: usize,
+        items: &'a [Record<'a>],
+        key_case: KeyCase,
+    },
+    Snapshot(Envelope<'a>),
+}
+
+impl Serialize for Items<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Items::Bare(items) => items.serialize(serializer),
+            Items::Snapshot(envelope) => envelope.serialize(serializer),
+            &Items::Page {
+                total,
+                offset,
+                items,
+                key_case,
+            } => {
+                let mut map = serializer.serialize_map(Some(3))?;
+                map.serialize_entry(&key_case.apply("total"), &total)?;
+                map.serialize_entry(&key_case.apply("offset"), &offset)?;
+                map.serialize_entry(&key_case.apply("items"), items)?;
+                map.end()
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Prometheus text exposition output of the window summary.
+
+use crate::formatter::{FormatConfig, WindowFormatter};
+use crate::models::WindowInfo;
+use crate::summary::WindowSummary;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Options for [`OutputFormat::Prometheus`](crate::OutputFormat::Prometheus).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrometheusOptions {
+    /// Prefix of every metric name, such as `desktop_`. Must itself be a valid
+    /// metric name, or empty.
+    pub prefix: String,
+}
+
+impl PrometheusOptions {
+    /// Whether the prefix can start a metric name.
+    pub(crate) fn is_valid(&self) -> bool {
+        self.prefix.chars().enumerate().all(|(i, c)| {
+            c.is_ascii_alphabetic() || c == '_' || c == ':' || (i > 0 && c.is_ascii_digit())
+        })
+    }
+}
+
+/// Escape a label value: backslash, double quote and line feed.
+fn escape_label(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl WindowFormatter {
+    // Prometheus format - gauges from the summary; size gauges are left out when sizes are dropped
+    pub(crate) fn format_prometheus(windows: &[WindowInfo], config: &FormatConfig) -> String {
+        let policy = &config.redaction;
+        let summary = WindowSummary::from_windows(windows);
+        let processes: Vec<String> = summary
+            .processes
+            .iter()
+            .map(|p| policy.show("process", &p.process))
+            .collect();
+        let prefix = &config.prometheus.prefix;
+        let mut output = String::new();
+        let mut family = |name: &str, help: &str, samples: Vec<(Option<&str>, u64)>| {
+            let _ = writeln!(output, "# HELP {}{} {}", prefix, name, help);
+            let _ = writeln!(output, "# TYPE {}{} gauge", prefix, name);
+            for (process, value) in samples {
+                let _ = match process {
+                    Some(process) => writeln!(
+                        output,
+                        "{}{}{{process=\"{}\"}} {}",
+                        prefix,
+                        name,
+                        escape_label(process),
+                        value
+                    ),
+                    None => writeln!(output, "{}{} {}", prefix, name, value),
+                };
+            }
+        };
+
+        family(
+            "windows_total",
+            "Number of windows.",
+            vec![(None, summary.total as u64)],
+        );
+        if !policy.drops_size() {
+            family(
+                "windows_zero_size",
+                "Number of windows with zero width or height.",
+                vec![(None, summary.zero_size as u64)],
+            );
+        }
+        family(
+            "window_count",
+            "Number of windows per process.",
+            summary
+                .processes
+                .iter()
+                .zip(&processes)
+                .map(|(p, process)| (Some(process.as_str()), p.count as u64))
+                .collect(),
+        );
+        if !policy.drops_size() {
+            family(
+                "window_area_pixels",
+                "Total area of the windows of each process in pixels.",
+                summary
+                    .processes
+                    .iter()
+                    .zip(&processes)
+                    .map(|(p, process)| (Some(process.as_str()), p.area))
+                    .collect(),
+            );
+        }
+        output
+    }
+}
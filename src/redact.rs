@@ -0,0 +1,293 @@
+//! Redaction and anonymization of window data.
+
+use crate::error::FormatError;
+use crate::formatter::TEMPLATE_FIELDS;
+use crate::models::WindowInfo;
+#[cfg(feature = "redaction-hash")]
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "redaction-hash")]
+use sha2::Sha256;
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+/// What to hide before window data leaves the machine.
+///
+/// Applied by every output format. Dropped fields are left out of JSON, YAML,
+/// CSV, TSV, XML and SQL output and out of the lines of Detail and Markdown
+/// definition lists. Formats that cannot leave out a cell show `<redacted>`
+/// instead, SVG and minimap output draw no rectangles when the geometry is
+/// dropped, and SQLite export stores `NULL`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionPolicy {
+    /// Keep only the first N characters of titles and replace the rest with `***`.
+    pub title_prefix: Option<usize>,
+    /// Replace the user name in home directories of `process_file`
+    /// (`C:\Users\alice`, `/home/alice`, `/Users/alice`) with `<user>`.
+    /// The rest of the path is kept as is, so paths that are not valid Unicode
+    /// are still written as [`PathEncoding`](crate::PathEncoding) asks.
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # {
+    /// use std::ffi::OsStr;
+    /// use std::os::unix::ffi::OsStrExt;
+    /// use std::path::PathBuf;
+    /// use window_enumerator_formatter::{
+    ///     FormatConfig, OutputFormat, PathEncoding, RedactionPolicy, TemplateFormat, WindowInfo,
+    /// };
+    ///
+    /// let file = PathBuf::from(OsStr::from_bytes(b"/home/alice/\xffapp"));
+    /// let window = WindowInfo::builder().process_file(file).build();
+    /// let config = FormatConfig::builder()
+    ///     .format(OutputFormat::Json)
+    ///     .template(TemplateFormat::Fields(vec!["file".into()]))
+    ///     .path_encoding(PathEncoding::Escaped)
+    ///     .redaction(RedactionPolicy {
+    ///         home_dirs: true,
+    ///         ..Default::default()
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     window.format(&config),
+    ///     r#"{"process_file":{"escaped":"/home/<user>/\\xffapp"}}"#
+    /// );
+    /// # }
+    /// ```
+    pub home_dirs: bool,
+    /// Replace PIDs and handles with an HMAC-SHA256 keyed with this string.
+    /// The same ID and salt always give the same value, so windows of one
+    /// process can still be grouped. The original IDs cannot be recovered
+    /// without the salt, so keep it secret and long enough not to be guessed.
+    ///
+    /// Requires the `redaction-hash` feature; without it, validation fails.
+    ///
+    /// ```
+    /// use window_enumerator_formatter::{
+    ///     FormatConfig, OutputFormat, RedactionPolicy, TemplateFormat, WindowInfo,
+    /// };
+    ///
+    /// let config = FormatConfig::builder()
+    ///     .format(OutputFormat::Json)
+    ///     .template(TemplateFormat::Fields(vec!["pid".into()]))
+    ///     .redaction(RedactionPolicy {
+    ///         hash_salt: Some("secret".to_string()),
+    ///         ..Default::default()
+    ///     })
+    ///     .build();
+    ///
+    /// # #[cfg(feature = "redaction-hash")]
+    /// # {
+    /// let window = WindowInfo::builder().pid(1234).build();
+    /// assert_eq!(window.format(&config.unwrap()), r#"{"pid":4082674261}"#);
+    /// # }
+    /// # #[cfg(not(feature = "redaction-hash"))]
+    /// # assert!(config.is_err());
+    /// ```
+    pub hash_salt: Option<String>,
+    /// Fields to drop, by template field name (`"file"`, `"pid"`, `"x"`, ...).
+    pub drop: Vec<String>,
+}
+
+/// Replacement for the user name in home directories.
+const USER_PLACEHOLDER: &str = "<user>";
+
+/// Directories whose children are user homes.
+const HOME_PARENTS: [&str; 3] = ["Users", "home", "Documents and Settings"];
+
+/// Text appended to titles cut by [`RedactionPolicy::title_prefix`].
+const TITLE_MASK: &str = "***";
+
+/// Shown in place of dropped fields by formats that cannot leave them out.
+pub(crate) const REDACTED: &str = "<redacted>";
+
+impl RedactionPolicy {
+    /// Whether the policy changes anything.
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// Whether `field` is dropped.
+    pub fn drops(&self, field: &str) -> bool {
+        self.drop
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(field))
+    }
+
+    /// `value` as text, or [`REDACTED`] if `field` is dropped.
+    pub(crate) fn show(&self, field: &str, value: impl Display) -> String {
+        if self.drops(field) {
+            REDACTED.to_string()
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// Whether the width or height is dropped, so areas are unknown.
+    pub(crate) fn drops_size(&self) -> bool {
+        self.drops("width") || self.drops("height")
+    }
+
+    /// Whether any coordinate is dropped, so windows cannot be drawn.
+    pub(crate) fn drops_geometry(&self) -> bool {
+        self.drops_size() || self.drops("x") || self.drops("y")
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), FormatError> {
+        if cfg!(not(feature = "redaction-hash")) && self.hash_salt.is_some() {
+            return Err(FormatError::InvalidConfig {
+                message: "hash_salt requires the `redaction-hash` feature".to_string(),
+            });
+        }
+        match self
+            .drop
+            .iter()
+            .find(|field| !TEMPLATE_FIELDS.contains(&field.to_lowercase().as_str()))
+        {
+            Some(field) => Err(FormatError::InvalidField {
+                field: field.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn mask_title<'a>(&self, title: &'a str) -> Cow<'a, str> {
+        match self.title_prefix {
+            Some(keep) if title.chars().count() > keep => {
+                let kept: String = title.chars().take(keep).collect();
+                Cow::Owned(format!("{}{}", kept, TITLE_MASK))
+            }
+            _ => Cow::Borrowed(title),
+        }
+    }
+
+    // HMAC-SHA256 keyed with the salt, so IDs cannot be recovered without it
+    #[cfg(feature = "redaction-hash")]
+    fn hash_id(&self, kind: &str, id: u64) -> Option<u64> {
+        let salt = self.hash_salt.as_ref()?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(salt.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(kind.as_bytes());
+        mac.update(&[0]);
+        mac.update(&id.to_le_bytes());
+        let digest = mac.finalize().into_bytes();
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        Some(u64::from_le_bytes(bytes))
+    }
+
+    // Unreachable with a validated config, which rejects a salt without the feature
+    #[cfg(not(feature = "redaction-hash"))]
+    fn hash_id(&self, _kind: &str, _id: u64) -> Option<u64> {
+        None
+    }
+}
+
+// Replace the user name segment after `Users`, `home` or `Documents and Settings`
+// in the OS representation of the path, so paths that are not valid Unicode stay
+// lossless for `PathEncoding`
+fn redact_home(path: &Path) -> Option<PathBuf> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::{OsStrExt, OsStringExt};
+        let placeholder: Vec<u8> = USER_PLACEHOLDER.bytes().collect();
+        redact_home_units(path.as_os_str().as_bytes(), &placeholder)
+            .map(|bytes| PathBuf::from(std::ffi::OsString::from_vec(bytes)))
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::{OsStrExt, OsStringExt};
+        let units: Vec<u16> = path.as_os_str().encode_wide().collect();
+        let placeholder: Vec<u16> = USER_PLACEHOLDER.encode_utf16().collect();
+        redact_home_units(&units, &placeholder)
+            .map(|units| PathBuf::from(std::ffi::OsString::from_wide(&units)))
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        let placeholder: Vec<u8> = USER_PLACEHOLDER.bytes().collect();
+        redact_home_units(path.to_string_lossy().as_bytes(), &placeholder)
+            .map(|bytes| PathBuf::from(String::from_utf8_lossy(&bytes).into_owned()))
+    }
+}
+
+// Code units below 0x80 are ASCII characters in both UTF-8 and UTF-16
+fn redact_home_units<T: Copy + Into<u32>>(path: &[T], placeholder: &[T]) -> Option<Vec<T>> {
+    let is_separator = |unit: &T| matches!((*unit).into(), 0x2f | 0x5c);
+    let is_home = |name: &[T]| {
+        HOME_PARENTS.iter().any(|home| {
+            name.len() == home.len()
+                && name.iter().zip(home.bytes()).all(|(&unit, byte)| {
+                    u8::try_from(unit.into()).is_ok_and(|unit| unit.eq_ignore_ascii_case(&byte))
+                })
+        })
+    };
+
+    let mut output = Vec::with_capacity(path.len());
+    let mut parent: &[T] = &[];
+    let mut changed = false;
+
+    for segment in path.split_inclusive(is_separator) {
+        let name = match segment.split_last() {
+            Some((last, name)) if is_separator(last) => name,
+            _ => segment,
+        };
+        if is_home(parent) && !name.is_empty() {
+            output.extend_from_slice(placeholder);
+            output.extend_from_slice(&segment[name.len()..]);
+            changed = true;
+        } else {
+            output.extend_from_slice(segment);
+        }
+        parent = name;
+    }
+
+    changed.then_some(output)
+}
+
+impl WindowInfo {
+    // Copy of the window with the policy applied, borrowed when the policy is inactive
+    pub(crate) fn redacted(&self, policy: &RedactionPolicy) -> Cow<'_, WindowInfo> {
+        if !policy.is_active() {
+            return Cow::Borrowed(self);
+        }
+
+        let mut window = self.clone();
+        if let Cow::Owned(title) = policy.mask_title(&self.title) {
+            window.title = title;
+        }
+        if policy.home_dirs {
+            if let Some(file) = redact_home(&self.process_file) {
+                window.process_file = file;
+            }
+        }
+        if let Some(pid) = policy.hash_id("pid", self.pid as u64) {
+            window.pid = pid as u32;
+        }
+        if let Some(hwnd) = policy.hash_id("hwnd", self.hwnd as u64) {
+            window.hwnd = (hwnd & isize::MAX as u64) as isize;
+        }
+
+        for field in &policy.drop {
+            match field.to_lowercase().as_str() {
+                "index" => window.index = 0,
+                "hwnd" => window.hwnd = 0,
+                "pid" => window.pid = 0,
+                "title" => window.title.clear(),
+                "class" => window.class_name.clear(),
+                "process" => window.process_name.clear(),
+                "file" => window.process_file = PathBuf::new(),
+                "x" => window.position.x = 0,
+                "y" => window.position.y = 0,
+                "width" => window.position.width = 0,
+                "height" => window.position.height = 0,
+                _ => {}
+            }
+        }
+        Cow::Owned(window)
+    }
+}
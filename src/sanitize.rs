@@ -0,0 +1,130 @@
+//! Control-character and ANSI escape sanitization for human-readable formats.
+
+use crate::models::WindowInfo;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+/// How control characters in window strings are rendered by human-readable formats.
+///
+/// Covers C0 and C1 control characters, DEL, ANSI escape sequences and Unicode
+/// bidirectional overrides. A title such as `"\x1b[2J"` would otherwise clear
+/// the terminal, and embedded newlines would break table rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ControlChars {
+    /// Remove escape sequences and control characters; tabs and line breaks
+    /// become spaces.
+    #[default]
+    Strip,
+    /// Show control characters as escapes such as `\x1b` or `\u{202e}`, for debugging.
+    Escape,
+    /// Output strings unchanged.
+    Raw,
+}
+
+const ESC: char = '\x1b';
+const BEL: char = '\x07';
+
+// Unicode bidirectional embedding, override and isolate characters
+fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
+
+fn is_unsafe(c: char) -> bool {
+    c.is_control() || is_bidi_control(c)
+}
+
+/// Sanitize a string for display according to `mode`.
+pub(crate) fn sanitize(text: &str, mode: ControlChars) -> Cow<'_, str> {
+    if mode == ControlChars::Raw || !text.chars().any(is_unsafe) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if !is_unsafe(c) {
+            output.push(c);
+            continue;
+        }
+
+        match mode {
+            ControlChars::Escape => match c {
+                '\t' => output.push_str("\\t"),
+                '\n' => output.push_str("\\n"),
+                '\r' => output.push_str("\\r"),
+                c if (c as u32) < 0x80 => output.push_str(&format!("\\x{:02x}", c as u32)),
+                c => output.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            },
+            ControlChars::Strip => match c {
+                '\t' | '\n' | '\r' => output.push(' '),
+                ESC => skip_escape_sequence(&mut chars),
+                _ => {}
+            },
+            ControlChars::Raw => unreachable!("raw strings are returned unchanged"),
+        }
+    }
+    Cow::Owned(output)
+}
+
+impl WindowInfo {
+    // Copy of the window with every string field sanitized, borrowed when nothing changes
+    pub(crate) fn sanitized(&self, mode: ControlChars) -> Cow<'_, WindowInfo> {
+        let file = self.process_file.to_string_lossy();
+        let fields = [
+            sanitize(&self.title, mode),
+            sanitize(&self.class_name, mode),
+            sanitize(&self.process_name, mode),
+            sanitize(&file, mode),
+        ];
+        if fields.iter().all(|field| matches!(field, Cow::Borrowed(_))) {
+            return Cow::Borrowed(self);
+        }
+
+        let [title, class_name, process_name, file] = fields;
+        Cow::Owned(WindowInfo {
+            title: title.into_owned(),
+            class_name: class_name.into_owned(),
+            process_name: process_name.into_owned(),
+            process_file: match file {
+                Cow::Borrowed(_) => self.process_file.clone(),
+                Cow::Owned(file) => PathBuf::from(file),
+            },
+            ..self.clone()
+        })
+    }
+}
+
+// Skip the remainder of an ANSI escape sequence after its ESC
+fn skip_escape_sequence(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    match chars.peek() {
+        // CSI: parameters and intermediates up to a final byte in @..~
+        Some('[') => {
+            chars.next();
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+        // OSC and other strings: terminated by BEL or ESC \
+        Some(']' | 'P' | 'X' | '^' | '_') => {
+            chars.next();
+            while let Some(c) = chars.next() {
+                if c == BEL {
+                    break;
+                }
+                if c == ESC && chars.peek() == Some(&'\\') {
+                    chars.next();
+                    break;
+                }
+            }
+        }
+        // Two-character sequences such as ESC c
+        Some(c) if !c.is_control() => {
+            chars.next();
+        }
+        _ => {}
+    }
+}
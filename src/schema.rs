@@ -0,0 +1,103 @@
+//! JSON Schema for the structured output formats.
+
+use crate::models::WindowInfo;
+use crate::summary::WindowSummary;
+use schemars::generate::SchemaSettings;
+use schemars::JsonSchema;
+use serde_json::Value;
+
+/// Layout of a snapshot envelope, mirroring what `OutputFormat::Json` writes
+/// with [`FormatConfig::snapshot`](crate::FormatConfig::snapshot) set.
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+struct SnapshotEnvelope {
+    /// Version of the window record layout.
+    schema_version: u32,
+    /// When the snapshot was written, as an RFC 3339 UTC timestamp.
+    generated_at: String,
+    /// Host name of the machine that wrote the snapshot, unless redacted.
+    host: Option<String>,
+    /// Version of this crate that wrote the snapshot.
+    tool_version: String,
+    /// Number of windows.
+    count: usize,
+    /// The windows.
+    windows: Vec<WindowInfo>,
+}
+
+fn schema_for<T: JsonSchema>() -> Value {
+    SchemaSettings::draft2020_12()
+        .for_serialize()
+        .into_generator()
+        .into_root_schema_for::<T>()
+        .to_value()
+}
+
+/// JSON Schema (draft 2020-12) of a window record as written by JSON, YAML
+/// and NDJSON output with default serialization options and no field selection.
+///
+/// ```
+/// use window_enumerator_formatter::{window_schema, OutputFormat, WindowInfo, WindowPosition};
+///
+/// let window = WindowInfo::builder()
+///     .hwnd(0x10318)
+///     .title("Editor".to_string())
+///     .position(WindowPosition { x: -8, y: 0, width: 800, height: 600 })
+///     .build();
+/// let record: serde_json::Value =
+///     serde_json::from_str(&window.format_with(OutputFormat::Json)).unwrap();
+///
+/// let schema = window_schema();
+/// assert!(jsonschema::is_valid(&schema, &record));
+/// assert!(!jsonschema::is_valid(&schema, &serde_json::json!({ "title": "Editor" })));
+///
+/// // Paths that are not valid Unicode may be written escaped or in base64
+/// let mut escaped = record.clone();
+/// escaped["process_file"] = serde_json::json!({ "escaped": "/opt/\\xff" });
+/// assert!(jsonschema::is_valid(&schema, &escaped));
+/// let mut base64 = record.clone();
+/// base64["process_file"] = serde_json::json!({ "base64": "L29wdC//" });
+/// assert!(jsonschema::is_valid(&schema, &base64));
+/// base64["process_file"] = serde_json::json!({ "hex": "2f6f7074" });
+/// assert!(!jsonschema::is_valid(&schema, &base64));
+/// ```
+pub fn window_schema() -> Value {
+    schema_for::<WindowInfo>()
+}
+
+/// JSON Schema (draft 2020-12) of a snapshot envelope written by JSON and YAML
+/// output with [`FormatConfig::snapshot`](crate::FormatConfig::snapshot) set.
+///
+/// ```
+/// use window_enumerator_formatter::{
+///     snapshot_schema, FormatConfig, OutputFormat, WindowInfo, WindowListFormat,
+/// };
+///
+/// let windows = vec![WindowInfo::builder().title("Editor".to_string()).build()];
+/// let config = FormatConfig::builder()
+///     .format(OutputFormat::Json)
+///     .snapshot(true)
+///     .build()
+///     .unwrap();
+/// let snapshot: serde_json::Value =
+///     serde_json::from_str(&windows.format_output(&config)).unwrap();
+///
+/// assert!(jsonschema::is_valid(&snapshot_schema(), &snapshot));
+/// ```
+pub fn snapshot_schema() -> Value {
+    schema_for::<SnapshotEnvelope>()
+}
+
+/// JSON Schema (draft 2020-12) of a serialized [`WindowSummary`].
+///
+/// ```
+/// use window_enumerator_formatter::{summary_schema, WindowInfo, WindowSummary};
+///
+/// let windows = vec![WindowInfo::builder().process_name("editor.exe".to_string()).build()];
+/// let summary = serde_json::to_value(WindowSummary::from_windows(&windows)).unwrap();
+///
+/// assert!(jsonschema::is_valid(&summary_schema(), &summary));
+/// ```
+pub fn summary_schema() -> Value {
+    schema_for::<WindowSummary>()
+}
@@ -0,0 +1,80 @@
+//! Key naming and value representation for JSON and YAML output.
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Case of JSON and YAML keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyCase {
+    /// `class_name`
+    #[default]
+    Snake,
+    /// `className`
+    Camel,
+    /// `ClassName`
+    Pascal,
+    /// `class-name`
+    Kebab,
+}
+
+impl KeyCase {
+    /// Convert a snake_case key to this case.
+    pub fn apply(self, key: &str) -> Cow<'_, str> {
+        match self {
+            KeyCase::Snake => Cow::Borrowed(key),
+            KeyCase::Kebab => Cow::Owned(key.replace('_', "-")),
+            KeyCase::Camel | KeyCase::Pascal => {
+                let mut output = String::with_capacity(key.len());
+                for (i, word) in key.split('_').enumerate() {
+                    let mut chars = word.chars();
+                    if let Some(first) = chars.next() {
+                        if i == 0 && self == KeyCase::Camel {
+                            output.push(first);
+                        } else {
+                            output.extend(first.to_uppercase());
+                        }
+                        output.push_str(chars.as_str());
+                    }
+                }
+                Cow::Owned(output)
+            }
+        }
+    }
+}
+
+/// How window handles are written to JSON and YAML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HandleEncoding {
+    /// An integer, such as `66328`.
+    #[default]
+    Decimal,
+    /// A string matching text formats, such as `"0x10318"`.
+    Hex,
+}
+
+/// How process file paths are written to JSON and YAML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PathStyle {
+    /// Unchanged, with the separators of the platform that produced them.
+    #[default]
+    Native,
+    /// With `\` replaced by `/`, for consumers on other platforms.
+    Forward,
+}
+
+/// Representation options for [`OutputFormat::Json`](crate::OutputFormat::Json),
+/// [`OutputFormat::JsonPretty`](crate::OutputFormat::JsonPretty) and
+/// [`OutputFormat::Yaml`](crate::OutputFormat::Yaml).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SerializationOptions {
+    /// Case of every key, including `position` and page envelope keys.
+    pub key_case: KeyCase,
+    /// How `hwnd` is written.
+    pub handle: HandleEncoding,
+    /// How `process_file` is written.
+    pub path_style: PathStyle,
+}
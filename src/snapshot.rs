@@ -0,0 +1,387 @@
+//! Versioned snapshot envelopes, and parsing of saved window lists.
+
+use crate::encoding::decode;
+use crate::error::FormatError;
+use crate::formatter::OutputFormat;
+use crate::models::WindowInfo;
+use crate::projection::Record;
+use crate::serialization::KeyCase;
+#[cfg(feature = "toml")]
+use crate::toml_format::WINDOW_TABLE;
+use crate::xml::parse_xml;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Version of the window record layout written in snapshot envelopes.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Upgrades of a raw window record from each schema version to the next.
+///
+/// `MIGRATIONS[0]` turns a version 1 record into a version 2 record, and so on.
+/// When `WindowInfo` gains or renames a field, bump [`SCHEMA_VERSION`] and add
+/// the step here so older snapshots keep parsing. Fields added with a default
+/// need no step, as missing fields fall back to their defaults.
+const MIGRATIONS: &[fn(&mut Value)] = &[];
+
+const _: () = assert!(MIGRATIONS.len() + 1 == SCHEMA_VERSION as usize);
+
+/// Top-level keys of a window record, which tell a single record from an envelope.
+const WINDOW_FIELDS: [&str; 8] = [
+    "index",
+    "hwnd",
+    "pid",
+    "title",
+    "class_name",
+    "process_name",
+    "process_file",
+    "position",
+];
+
+/// Metadata written before the windows of a snapshot.
+pub(crate) struct SnapshotHeader {
+    pub generated_at: String,
+    pub host: Option<String>,
+    count: usize,
+    key_case: KeyCase,
+}
+
+impl SnapshotHeader {
+    /// Header for `count` windows written now on this machine. The host name is
+    /// left out when `anonymous` is set.
+    pub(crate) fn now(count: usize, key_case: KeyCase, anonymous: bool) -> Self {
+        Self {
+            generated_at: rfc3339(SystemTime::now()),
+            host: if anonymous { None } else { host_name() },
+            count,
+            key_case,
+        }
+    }
+
+    pub(crate) fn serialize_entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        let key = |key| self.key_case.apply(key);
+        map.serialize_entry(&key("schema_version"), &SCHEMA_VERSION)?;
+        map.serialize_entry(&key("generated_at"), &self.generated_at)?;
+        map.serialize_entry(&key("host"), &self.host)?;
+        map.serialize_entry(&key("tool_version"), env!("CARGO_PKG_VERSION"))?;
+        map.serialize_entry(&key("count"), &self.count)
+    }
+}
+
+impl SnapshotHeader {
+    /// Metadata as attribute values, without `count`.
+    pub(crate) fn attributes(&self) -> Vec<(&'static str, String)> {
+        let mut attributes = vec![
+            ("schema_version", SCHEMA_VERSION.to_string()),
+            ("generated_at", self.generated_at.clone()),
+        ];
+        if let Some(host) = &self.host {
+            attributes.push(("host", host.clone()));
+        }
+        attributes.push(("tool_version", env!("CARGO_PKG_VERSION").to_string()));
+        attributes
+    }
+}
+
+impl Serialize for SnapshotHeader {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        self.serialize_entries(&mut map)?;
+        map.end()
+    }
+}
+
+/// A snapshot header followed by its windows.
+pub(crate) struct Envelope<'a> {
+    pub header: SnapshotHeader,
+    pub windows: &'a [Record<'a>],
+}
+
+impl Serialize for Envelope<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        self.header.serialize_entries(&mut map)?;
+        map.serialize_entry(&self.header.key_case.apply("windows"), self.windows)?;
+        map.end()
+    }
+}
+
+/// A window list read back from JSON, YAML, NDJSON, XML or TOML output.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    /// Schema version the snapshot was written with. Windows are migrated to
+    /// [`SCHEMA_VERSION`] while parsing. Bare lists count as version 1.
+    pub schema_version: u32,
+    /// When the snapshot was written, as an RFC 3339 UTC timestamp.
+    pub generated_at: Option<String>,
+    /// Host name of the machine that wrote the snapshot.
+    pub host: Option<String>,
+    /// Version of this crate that wrote the snapshot.
+    pub tool_version: Option<String>,
+    /// The windows.
+    pub windows: Vec<WindowInfo>,
+}
+
+impl Snapshot {
+    /// Parse a snapshot envelope, a bare window list or a single window record,
+    /// written as JSON, YAML, NDJSON with or without a header line, XML in any
+    /// [`XmlLayout`](crate::XmlLayout), or TOML `[[window]]` tables (with the
+    /// `toml` feature).
+    ///
+    /// Keys may be in any [`KeyCase`]; missing fields take their default
+    /// values, and `hwnd` may be a number or a `0x..` string. XML round trips
+    /// are lossy for control characters: escapes such as `\x1b` are read back
+    /// as text.
+    ///
+    /// ```
+    /// use window_enumerator_formatter::{
+    ///     FormatConfig, KeyCase, OutputFormat, SerializationOptions, Snapshot, WindowInfo,
+    ///     WindowListFormat, SCHEMA_VERSION,
+    /// };
+    ///
+    /// let windows = vec![WindowInfo::builder()
+    ///     .class_name("Notepad".to_string())
+    ///     .process_name("notepad.exe".to_string())
+    ///     .build()];
+    /// for format in [OutputFormat::Json, OutputFormat::Ndjson] {
+    ///     let config = FormatConfig::builder()
+    ///         .format(format)
+    ///         .snapshot(true)
+    ///         .serialization(SerializationOptions {
+    ///             key_case: KeyCase::Camel,
+    ///             ..Default::default()
+    ///         })
+    ///         .build()
+    ///         .unwrap();
+    ///     let snapshot = Snapshot::parse(&windows.format_output(&config)).unwrap();
+    ///     assert_eq!(snapshot.schema_version, SCHEMA_VERSION);
+    ///     assert!(snapshot.generated_at.is_some());
+    ///     assert_eq!(snapshot.windows[0].class_name, "Notepad");
+    ///     assert_eq!(snapshot.windows[0].process_name, "notepad.exe");
+    /// }
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, FormatError> {
+        let text = text.trim_start_matches('\u{feff}');
+        if text.trim_start().starts_with('<') {
+            return Self::from_value(parse_xml(text)?);
+        }
+        let value = match serde_json::from_str(text) {
+            Ok(value) => value,
+            Err(error) => match parse_ndjson(text) {
+                Some(value) => value,
+                None => match parse_toml(text) {
+                    Some(value) => value,
+                    None if text.trim_start().starts_with(['[', '{']) => return Err(error.into()),
+                    None => serde_yaml::from_str(text)?,
+                },
+            },
+        };
+        Self::from_value(value)
+    }
+
+    /// Parse output of [`WindowFormatter::format_windows_bytes`](crate::WindowFormatter::format_windows_bytes)
+    /// or [`WindowFormatter::format_window_bytes`](crate::WindowFormatter::format_window_bytes)
+    /// in `format`. MessagePack and CBOR (with the `messagepack` and `cbor`
+    /// features) are decoded; text in any [`OutputEncoding`](crate::OutputEncoding)
+    /// goes through [`Snapshot::parse`].
+    pub fn parse_bytes(bytes: &[u8], format: OutputFormat) -> Result<Self, FormatError> {
+        match format {
+            #[cfg(feature = "messagepack")]
+            OutputFormat::MessagePack => Self::from_value(rmp_serde::from_slice(bytes)?),
+            #[cfg(feature = "cbor")]
+            OutputFormat::Cbor => Self::from_value(ciborium::from_reader(bytes)?),
+            _ => Self::parse(&decode(bytes).map_err(|message| invalid(&message))?),
+        }
+    }
+
+    fn from_value(mut value: Value) -> Result<Self, FormatError> {
+        snake_case_keys(&mut value);
+        let (header, mut windows) = match value {
+            Value::Array(windows) => (Map::new(), windows),
+            Value::Object(mut header) => match header.remove("windows") {
+                Some(Value::Array(windows)) => (header, windows),
+                Some(_) => return Err(invalid("snapshot `windows` must be a list")),
+                // A header line without records, as NDJSON writes an empty snapshot
+                None if header.contains_key("schema_version") => (header, Vec::new()),
+                // A single record, as NDJSON writes a one-window list
+                None if WINDOW_FIELDS
+                    .iter()
+                    .any(|field| header.contains_key(*field)) =>
+                {
+                    (Map::new(), vec![Value::Object(header)])
+                }
+                None => return Err(invalid("snapshot envelope has no `windows` list")),
+            },
+            _ => return Err(invalid("expected a window list or a snapshot envelope")),
+        };
+
+        let schema_version = match header.get("schema_version") {
+            None => 1,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| invalid("`schema_version` must be a positive integer"))?,
+        };
+        if schema_version == 0 || schema_version > SCHEMA_VERSION {
+            return Err(FormatError::UnsupportedSchema {
+                found: schema_version,
+                supported: SCHEMA_VERSION,
+            });
+        }
+
+        for migrate in &MIGRATIONS[schema_version as usize - 1..] {
+            windows.iter_mut().for_each(migrate);
+        }
+        let windows = windows
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<Result<Vec<WindowInfo>, _>>()?;
+
+        if let Some(count) = header.get("count").and_then(Value::as_u64) {
+            if count != windows.len() as u64 {
+                return Err(invalid(&format!(
+                    "snapshot declares {} windows but contains {}",
+                    count,
+                    windows.len()
+                )));
+            }
+        }
+
+        let text = |key: &str| header.get(key).and_then(Value::as_str).map(String::from);
+        Ok(Self {
+            schema_version,
+            generated_at: text("generated_at"),
+            host: text("host"),
+            tool_version: text("tool_version"),
+            windows,
+        })
+    }
+}
+
+fn invalid(message: &str) -> FormatError {
+    FormatError::Other {
+        message: message.to_string(),
+    }
+}
+
+// One JSON object per line; a first line with `schema_version` is the header
+fn parse_ndjson(text: &str) -> Option<Value> {
+    let mut records = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| match serde_json::from_str(line) {
+            Ok(Value::Object(record)) => Some(record),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    match records.first() {
+        Some(first) if first.keys().any(|key| snake_case(key) == "schema_version") => {
+            let mut header = records.remove(0);
+            let windows = records.into_iter().map(Value::Object).collect();
+            header.insert("windows".to_string(), Value::Array(windows));
+            Some(Value::Object(header))
+        }
+        _ => Some(Value::Array(
+            records.into_iter().map(Value::Object).collect(),
+        )),
+    }
+}
+
+// Rename keys written in any `KeyCase` back to snake_case, recursively
+fn snake_case_keys(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            *map = std::mem::take(map)
+                .into_iter()
+                .map(|(key, mut value)| {
+                    snake_case_keys(&mut value);
+                    (snake_case(&key), value)
+                })
+                .collect();
+        }
+        Value::Array(values) => values.iter_mut().for_each(snake_case_keys),
+        _ => {}
+    }
+}
+
+// Reverse `KeyCase::apply`: `className`, `ClassName` and `class-name` become `class_name`
+fn snake_case(key: &str) -> String {
+    let mut output = String::with_capacity(key.len() + 4);
+    for (i, c) in key.chars().enumerate() {
+        match c {
+            '-' => output.push('_'),
+            _ if c.is_uppercase() => {
+                if i > 0 && !output.ends_with('_') {
+                    output.push('_');
+                }
+                output.extend(c.to_lowercase());
+            }
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+// A TOML document with `[[window]]` tables, as an envelope
+#[cfg(feature = "toml")]
+fn parse_toml(text: &str) -> Option<Value> {
+    let Ok(Value::Object(mut document)) = toml::from_str(text) else {
+        return None;
+    };
+    let windows = document.remove(WINDOW_TABLE)?;
+    document.insert("windows".to_string(), windows);
+    Some(Value::Object(document))
+}
+
+#[cfg(not(feature = "toml"))]
+fn parse_toml(_text: &str) -> Option<Value> {
+    None
+}
+
+fn host_name() -> Option<String> {
+    let non_empty = |name: String| {
+        let name = name.trim();
+        (!name.is_empty()).then(|| name.to_string())
+    };
+    ["COMPUTERNAME", "HOSTNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().and_then(non_empty))
+        .or_else(|| {
+            std::fs::read_to_string("/etc/hostname")
+                .ok()
+                .and_then(non_empty)
+        })
+}
+
+// RFC 3339 UTC timestamp, using the days-to-civil-date algorithm by Howard Hinnant
+fn rfc3339(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, time) = ((seconds / 86_400) as i64, seconds % 86_400);
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3_600,
+        time / 60 % 60,
+        time % 60
+    )
+}
@@ -0,0 +1,291 @@
+//! SQL `CREATE TABLE` and `INSERT` output.
+
+use crate::formatter::{FormatConfig, WindowFormatter};
+use crate::models::WindowInfo;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// SQL dialect of [`OutputFormat::Sql`](crate::OutputFormat::Sql).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SqlDialect {
+    /// SQLite: `INTEGER` and `TEXT` columns.
+    #[default]
+    Sqlite,
+    /// PostgreSQL: `BIGINT`, `INTEGER` and `TEXT` columns. PostgreSQL text
+    /// cannot hold NUL characters, so they are replaced with U+FFFD.
+    Postgres,
+}
+
+/// Options for [`OutputFormat::Sql`](crate::OutputFormat::Sql).
+///
+/// An empty list is written as the `CREATE TABLE` statement alone, or as
+/// nothing without [`create_table`](Self::create_table), so the output can
+/// always be piped into a database shell.
+///
+/// ```
+/// use window_enumerator_formatter::{
+///     FormatConfig, OutputFormat, RedactionPolicy, SqlOptions, WindowInfo, WindowListFormat,
+/// };
+///
+/// let config = |sql: SqlOptions| {
+///     FormatConfig::builder().format(OutputFormat::Sql).sql(sql).build().unwrap()
+/// };
+/// let windows: Vec<WindowInfo> = Vec::new();
+/// let sql = windows.format_output(&config(SqlOptions::default()));
+/// assert!(sql.starts_with("CREATE TABLE \"windows\" (") && sql.ends_with(");\n"));
+/// assert!(!sql.contains("INSERT"));
+///
+/// let options = SqlOptions { create_table: false, ..Default::default() };
+/// assert_eq!(windows.format_output(&config(options)), "");
+///
+/// // A table needs at least one column
+/// let drop_all = RedactionPolicy {
+///     drop: ["index", "hwnd", "pid", "title", "class", "process", "file", "x", "y", "width", "height"]
+///         .map(String::from)
+///         .to_vec(),
+///     ..Default::default()
+/// };
+/// assert!(FormatConfig::builder()
+///     .format(OutputFormat::Sql)
+///     .redaction(drop_all)
+///     .build()
+///     .is_err());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SqlOptions {
+    /// Name of the windows table, quoted as an identifier.
+    pub table: String,
+    /// Dialect of column types and string literals.
+    pub dialect: SqlDialect,
+    /// Rows per `INSERT` statement. SQLite accepts at most 500 by default.
+    pub batch_size: usize,
+    /// Whether to start with a `CREATE TABLE` statement.
+    pub create_table: bool,
+}
+
+impl Default for SqlOptions {
+    fn default() -> Self {
+        Self {
+            table: "windows".to_string(),
+            dialect: SqlDialect::Sqlite,
+            batch_size: 500,
+            create_table: true,
+        }
+    }
+}
+
+/// Storage class of a column.
+#[derive(Clone, Copy)]
+enum Kind {
+    Integer,
+    BigInteger,
+    Text,
+}
+
+/// A column value of one window.
+pub(crate) enum SqlValue<'a> {
+    Integer(i64),
+    Text(Cow<'a, str>),
+}
+
+/// A column of the windows table.
+pub(crate) struct Column {
+    pub name: &'static str,
+    /// Name in [`TEMPLATE_FIELDS`](crate::TEMPLATE_FIELDS), used for redaction.
+    pub field: &'static str,
+    kind: Kind,
+    pub value: fn(&WindowInfo) -> SqlValue<'_>,
+}
+
+pub(crate) const COLUMNS: [Column; 11] = [
+    Column {
+        name: "index",
+        field: "index",
+        kind: Kind::BigInteger,
+        value: |w| SqlValue::Integer(w.index as i64),
+    },
+    Column {
+        name: "hwnd",
+        field: "hwnd",
+        kind: Kind::BigInteger,
+        value: |w| SqlValue::Integer(w.hwnd as i64),
+    },
+    Column {
+        name: "pid",
+        field: "pid",
+        kind: Kind::BigInteger,
+        value: |w| SqlValue::Integer(w.pid as i64),
+    },
+    Column {
+        name: "title",
+        field: "title",
+        kind: Kind::Text,
+        value: |w| SqlValue::Text(Cow::Borrowed(&w.title)),
+    },
+    Column {
+        name: "class_name",
+        field: "class",
+        kind: Kind::Text,
+        value: |w| SqlValue::Text(Cow::Borrowed(&w.class_name)),
+    },
+    Column {
+        name: "process_name",
+        field: "process",
+        kind: Kind::Text,
+        value: |w| SqlValue::Text(Cow::Borrowed(&w.process_name)),
+    },
+    Column {
+        name: "process_file",
+        field: "file",
+        kind: Kind::Text,
+        value: |w| SqlValue::Text(w.process_file.to_string_lossy()),
+    },
+    Column {
+        name: "x",
+        field: "x",
+        kind: Kind::Integer,
+        value: |w| SqlValue::Integer(w.position.x as i64),
+    },
+    Column {
+        name: "y",
+        field: "y",
+        kind: Kind::Integer,
+        value: |w| SqlValue::Integer(w.position.y as i64),
+    },
+    Column {
+        name: "width",
+        field: "width",
+        kind: Kind::Integer,
+        value: |w| SqlValue::Integer(w.position.width as i64),
+    },
+    Column {
+        name: "height",
+        field: "height",
+        kind: Kind::Integer,
+        value: |w| SqlValue::Integer(w.position.height as i64),
+    },
+];
+
+impl Column {
+    fn sql_type(&self, dialect: SqlDialect) -> &'static str {
+        match (self.kind, dialect) {
+            (Kind::Text, _) => "TEXT",
+            (_, SqlDialect::Sqlite) | (Kind::Integer, SqlDialect::Postgres) => "INTEGER",
+            (Kind::BigInteger, SqlDialect::Postgres) => "BIGINT",
+        }
+    }
+}
+
+/// Quote an identifier, doubling embedded quotes.
+pub(crate) fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Quote a string literal, doubling embedded quotes. SQLite literals cannot
+/// contain NUL, so it is spliced in with `char(0)`.
+fn quote_literal(text: &str, dialect: SqlDialect) -> String {
+    let quote = |text: &str| format!("'{}'", text.replace('\'', "''"));
+    if !text.contains('\0') {
+        return quote(text);
+    }
+    match dialect {
+        SqlDialect::Sqlite => text
+            .split('\0')
+            .map(quote)
+            .collect::<Vec<_>>()
+            .join(" || char(0) || "),
+        SqlDialect::Postgres => quote(&text.replace('\0', "\u{fffd}")),
+    }
+}
+
+/// `CREATE TABLE` statement, with a leading `snapshot_id` column for SQLite
+/// export. SQLite export stores dropped fields as `NULL`, so its columns are nullable.
+pub(crate) fn create_table(
+    table: &str,
+    columns: &[&Column],
+    dialect: SqlDialect,
+    snapshot_id: Option<&str>,
+) -> String {
+    let mut definitions: Vec<String> = snapshot_id
+        .map(|snapshots| {
+            format!(
+                "\"snapshot_id\" INTEGER NOT NULL REFERENCES {}(\"id\")",
+                quote_identifier(snapshots)
+            )
+        })
+        .into_iter()
+        .collect();
+    let constraint = if snapshot_id.is_some() {
+        ""
+    } else {
+        " NOT NULL"
+    };
+    definitions.extend(columns.iter().map(|column| {
+        format!(
+            "{} {}{}",
+            quote_identifier(column.name),
+            column.sql_type(dialect),
+            constraint
+        )
+    }));
+    format!(
+        "CREATE TABLE {}{} (\n    {}\n);\n",
+        if snapshot_id.is_some() {
+            "IF NOT EXISTS "
+        } else {
+            ""
+        },
+        quote_identifier(table),
+        definitions.join(",\n    ")
+    )
+}
+
+impl WindowFormatter {
+    // SQL format - list
+    pub(crate) fn format_sql(windows: &[WindowInfo], config: &FormatConfig) -> String {
+        let options = &config.sql;
+        let columns: Vec<&Column> = COLUMNS
+            .iter()
+            .filter(|column| !config.redaction.drops(column.field))
+            .collect();
+
+        let mut output = String::new();
+        if options.create_table {
+            output.push_str(&create_table(
+                &options.table,
+                &columns,
+                options.dialect,
+                None,
+            ));
+        }
+
+        let names: Vec<String> = columns
+            .iter()
+            .map(|column| quote_identifier(column.name))
+            .collect();
+        for batch in windows.chunks(options.batch_size.max(1)) {
+            let rows: Vec<String> = batch
+                .iter()
+                .map(|window| {
+                    let values: Vec<String> = columns
+                        .iter()
+                        .map(|column| match (column.value)(window) {
+                            SqlValue::Integer(value) => value.to_string(),
+                            SqlValue::Text(text) => quote_literal(&text, options.dialect),
+                        })
+                        .collect();
+                    format!("    ({})", values.join(", "))
+                })
+                .collect();
+            output.push_str(&format!(
+                "INSERT INTO {} ({}) VALUES\n{};\n",
+                quote_identifier(&options.table),
+                names.join(", "),
+                rows.join(",\n")
+            ));
+        }
+        output
+    }
+}
@@ -0,0 +1,126 @@
+//! Export of window snapshots into a SQLite database.
+
+use crate::error::FormatError;
+use crate::formatter::{FormatConfig, WindowFormatter};
+use crate::models::WindowInfo;
+use crate::snapshot::SCHEMA_VERSION;
+use crate::sql::{create_table, quote_identifier, SqlDialect, SqlValue, COLUMNS};
+use rusqlite::types::Value;
+use rusqlite::Connection;
+use std::path::Path;
+
+/// Table holding one row per snapshot written by
+/// [`WindowFormatter::write_sqlite`].
+pub const SNAPSHOT_TABLE: &str = "snapshots";
+
+impl WindowFormatter {
+    /// Append windows to the SQLite database at `path` as a new snapshot,
+    /// creating it and its tables if needed, and return the snapshot id.
+    ///
+    /// See [`write_sqlite_connection`](Self::write_sqlite_connection).
+    pub fn write_sqlite(
+        path: impl AsRef<Path>,
+        windows: &[WindowInfo],
+        config: &FormatConfig,
+    ) -> Result<i64, FormatError> {
+        let mut connection = Connection::open(path)?;
+        Self::write_sqlite_connection(&mut connection, windows, config)
+    }
+
+    /// Append windows to a SQLite database as a new snapshot in one
+    /// transaction, and return the snapshot id.
+    ///
+    /// The snapshot is a row of [`SNAPSHOT_TABLE`] with `generated_at`, `host`,
+    /// `tool_version`, `schema_version` and `count`. Windows go to the table
+    /// named by [`SqlOptions::table`](crate::SqlOptions::table) with every
+    /// column and a leading `snapshot_id`; fields dropped by the redaction
+    /// policy are stored as `NULL` instead of leaving out their columns.
+    ///
+    /// ```
+    /// use rusqlite::Connection;
+    /// use window_enumerator_formatter::{FormatConfig, WindowFormatter, WindowInfo};
+    ///
+    /// let windows = vec![WindowInfo::builder().title("Tom's editor".to_string()).build()];
+    /// let mut connection = Connection::open_in_memory().unwrap();
+    /// let config = FormatConfig::default();
+    ///
+    /// let first = WindowFormatter::write_sqlite_connection(&mut connection, &windows, &config).unwrap();
+    /// let second = WindowFormatter::write_sqlite_connection(&mut connection, &windows, &config).unwrap();
+    /// assert_ne!(first, second);
+    ///
+    /// let title: String = connection
+    ///     .query_row("SELECT title FROM windows WHERE snapshot_id = ?1", [second], |row| row.get(0))
+    ///     .unwrap();
+    /// assert_eq!(title, "Tom's editor");
+    /// ```
+    pub fn write_sqlite_connection(
+        connection: &mut Connection,
+        windows: &[WindowInfo],
+        config: &FormatConfig,
+    ) -> Result<i64, FormatError> {
+        Self::check_paths(windows, config)?;
+        let table = &config.sql.table;
+        let columns: Vec<_> = COLUMNS.iter().collect();
+
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(&format!(
+            "CREATE TABLE IF NOT EXISTS {} (\n    \"id\" INTEGER PRIMARY KEY,\n    \
+             \"generated_at\" TEXT NOT NULL,\n    \"host\" TEXT,\n    \
+             \"tool_version\" TEXT NOT NULL,\n    \"schema_version\" INTEGER NOT NULL,\n    \
+             \"count\" INTEGER NOT NULL\n);\n{}",
+            quote_identifier(SNAPSHOT_TABLE),
+            create_table(table, &columns, SqlDialect::Sqlite, Some(SNAPSHOT_TABLE))
+        ))?;
+
+        let header = Self::snapshot_header(windows.len(), config);
+        transaction.execute(
+            &format!(
+                "INSERT INTO {} (\"generated_at\", \"host\", \"tool_version\", \
+                 \"schema_version\", \"count\") VALUES (?1, ?2, ?3, ?4, ?5)",
+                quote_identifier(SNAPSHOT_TABLE)
+            ),
+            rusqlite::params![
+                header.generated_at,
+                header.host,
+                env!("CARGO_PKG_VERSION"),
+                SCHEMA_VERSION,
+                windows.len() as i64
+            ],
+        )?;
+        let snapshot_id = transaction.last_insert_rowid();
+
+        {
+            let names: Vec<String> = columns
+                .iter()
+                .map(|column| quote_identifier(column.name))
+                .collect();
+            let placeholders: Vec<String> =
+                (1..=columns.len() + 1).map(|i| format!("?{}", i)).collect();
+            let mut insert = transaction.prepare(&format!(
+                "INSERT INTO {} (\"snapshot_id\", {}) VALUES ({})",
+                quote_identifier(table),
+                names.join(", "),
+                placeholders.join(", ")
+            ))?;
+            for window in windows {
+                let window = window.redacted(&config.redaction);
+                let window = window.with_path_encoding(config.path_encoding);
+                let values = std::iter::once(Value::Integer(snapshot_id)).chain(
+                    columns.iter().map(|column| {
+                        if config.redaction.drops(column.field) {
+                            return Value::Null;
+                        }
+                        match (column.value)(&window) {
+                            SqlValue::Integer(value) => Value::Integer(value),
+                            SqlValue::Text(text) => Value::Text(text.into_owned()),
+                        }
+                    }),
+                );
+                insert.execute(rusqlite::params_from_iter(values))?;
+            }
+        }
+
+        transaction.commit()?;
+        Ok(snapshot_id)
+    }
+}
@@ -0,0 +1,62 @@
+//! Aggregate statistics over a window list.
+
+use crate::models::{WindowInfo, WindowPosition};
+use serde::Serialize;
+
+/// Per-process window statistics.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ProcessSummary {
+    /// Process name.
+    pub process: String,
+    /// Number of windows owned by the process.
+    pub count: usize,
+    /// Total area of the process's windows in pixels.
+    pub area: u64,
+}
+
+/// Aggregate statistics over a window list.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WindowSummary {
+    /// Total number of windows.
+    pub total: usize,
+    /// Number of windows with zero width or height.
+    pub zero_size: usize,
+    /// Bounding box of all windows with a non-zero size.
+    pub bounds: Option<WindowPosition>,
+    /// Per-process statistics, most windows first.
+    pub processes: Vec<ProcessSummary>,
+}
+
+impl WindowSummary {
+    /// Compute the summary of a window list.
+    pub fn from_windows(windows: &[WindowInfo]) -> Self {
+        let mut processes: Vec<ProcessSummary> = Vec::new();
+        for window in windows {
+            let area = window.position.area();
+            match processes
+                .iter_mut()
+                .find(|p| p.process == window.process_name)
+            {
+                Some(entry) => {
+                    entry.count += 1;
+                    entry.area += area;
+                }
+                None => processes.push(ProcessSummary {
+                    process: window.process_name.clone(),
+                    count: 1,
+                    area,
+                }),
+            }
+        }
+        processes.sort_by(|a, b| b.count.cmp(&a.count).then(a.process.cmp(&b.process)));
+
+        Self {
+            total: windows.len(),
+            zero_size: windows.iter().filter(|w| w.position.area() == 0).count(),
+            bounds: WindowPosition::bounding_box(windows.iter().map(|w| &w.position)),
+            processes,
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! SVG rendering of window geometry.
+
+use crate::formatter::{FormatConfig, WindowFormatter};
+use crate::models::{WindowInfo, WindowPosition};
+use crate::xml::escape_xml;
+use serde::{Deserialize, Serialize};
+
+/// Options for [`OutputFormat::Svg`](crate::OutputFormat::Svg).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SvgOptions {
+    /// Rendered width in pixels; `None` renders at screen size.
+    pub max_width: Option<u32>,
+    /// Whether to label each rectangle with the window index and title.
+    pub show_labels: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            max_width: None,
+            show_labels: true,
+        }
+    }
+}
+
+impl WindowFormatter {
+    // SVG format - list
+    pub(crate) fn format_svg(windows: &[WindowInfo], config: &FormatConfig) -> String {
+        let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        output.push_str(&Self::svg_element(windows, config, &config.svg));
+        output
+    }
+
+    // Bare `<svg>` element, also embedded in HTML reports.
+    // Windows are drawn in reverse list order so the first window ends up on top.
+    pub(crate) fn svg_element(
+        windows: &[WindowInfo],
+        config: &FormatConfig,
+        options: &SvgOptions,
+    ) -> String {
+        // Windows whose geometry is dropped cannot be drawn
+        let policy = &config.redaction;
+        let drawn: &[WindowInfo] = if policy.drops_geometry() {
+            &[]
+        } else {
+            windows
+        };
+        let bounds =
+            WindowPosition::bounding_box(drawn.iter().map(|w| &w.position)).unwrap_or_default();
+        let (width, height) = match options.max_width {
+            Some(max) if bounds.width > max as i32 => (
+                max as i64,
+                bounds.height as i64 * max as i64 / bounds.width as i64,
+            ),
+            _ => (bounds.width as i64, bounds.height as i64),
+        };
+
+        let mut output = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\">\n",
+            width, height, bounds.x, bounds.y, bounds.width, bounds.height
+        );
+        output.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#fafafa\"/>\n",
+            bounds.x, bounds.y, bounds.width, bounds.height
+        ));
+
+        let font_size = (bounds.width.max(bounds.height) / 80).max(10);
+        for window in drawn.iter().rev().filter(|w| w.position.area() > 0) {
+            let p = &window.position;
+            let hue = Self::stable_hash(&window.process_name) % 360;
+            output.push_str(&format!(
+                "<g>\n<title>{} (PID: {})</title>\n",
+                escape_xml(&policy.show("title", &window.title), false),
+                policy.show("pid", window.pid)
+            ));
+            output.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+                 fill=\"hsl({}, 70%, 60%)\" fill-opacity=\"0.35\" \
+                 stroke=\"hsl({}, 70%, 35%)\" vector-effect=\"non-scaling-stroke\"/>\n",
+                p.x, p.y, p.width, p.height, hue, hue
+            ));
+            if options.show_labels {
+                let title = Self::display_title(window, config);
+                output.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" fill=\"hsl({}, 70%, 25%)\">[{}] {}</text>\n",
+                    p.x + font_size / 2,
+                    p.y + font_size * 3 / 2,
+                    font_size,
+                    hue,
+                    escape_xml(&policy.show("index", window.index), false),
+                    escape_xml(&title, false)
+                ));
+            }
+            output.push_str("</g>\n");
+        }
+
+        output.push_str("</svg>\n");
+        output
+    }
+}
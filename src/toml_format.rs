@@ -0,0 +1,40 @@
+//! TOML output as an array of `[[window]]` tables.
+
+use crate::formatter::{FormatConfig, WindowFormatter};
+use crate::projection::Record;
+use crate::snapshot::SnapshotHeader;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+
+/// Key of the window array of tables.
+pub(crate) const WINDOW_TABLE: &str = "window";
+
+/// Snapshot metadata as top-level keys, followed by the `[[window]]` tables.
+struct TomlDocument<'a> {
+    header: Option<SnapshotHeader>,
+    windows: &'a [Record<'a>],
+}
+
+impl Serialize for TomlDocument<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(header) = &self.header {
+            header.serialize_entries(&mut map)?;
+        }
+        map.serialize_entry(WINDOW_TABLE, self.windows)?;
+        map.end()
+    }
+}
+
+impl WindowFormatter {
+    // TOML format - list
+    pub(crate) fn format_toml(records: &[Record], config: &FormatConfig) -> String {
+        let document = TomlDocument {
+            header: config
+                .snapshot
+                .then(|| Self::snapshot_header(records.len(), config)),
+            windows: records,
+        };
+        toml::to_string(&document).unwrap_or_default()
+    }
+}
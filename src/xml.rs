@@ -0,0 +1,427 @@
+//! XML output and reading.
+
+use crate::error::FormatError;
+use crate::formatter::{FormatConfig, WindowFormatter};
+use crate::models::WindowInfo;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Which window fields [`OutputFormat::Xml`](crate::OutputFormat::Xml) writes
+/// as attributes and which as child elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum XmlLayout {
+    /// `index` and `hwnd` as attributes, other fields as elements, and
+    /// `<position x=".." y=".." width=".." height=".."/>`.
+    #[default]
+    Mixed,
+    /// Every field, including the position, as an attribute of `<window>`.
+    Attributes,
+    /// Every field as an element, with `<position>` holding `<x>`, `<y>`,
+    /// `<width>` and `<height>`.
+    Elements,
+}
+
+/// Options for [`OutputFormat::Xml`](crate::OutputFormat::Xml).
+///
+/// An empty list is still a document:
+///
+/// ```
+/// use window_enumerator_formatter::{OutputFormat, Snapshot, WindowInfo, WindowListFormat};
+///
+/// let windows: Vec<WindowInfo> = Vec::new();
+/// let xml = windows.format_with(OutputFormat::Xml);
+/// assert!(xml.ends_with("<windows count=\"0\"/>\n"));
+/// assert!(Snapshot::parse(&xml).unwrap().windows.is_empty());
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct XmlOptions {
+    /// Attribute or element mapping of window fields.
+    pub layout: XmlLayout,
+}
+
+/// Fields nested under `position` in serialized windows.
+const POSITION_FIELDS: [&str; 4] = ["x", "y", "width", "height"];
+
+/// Fields read back as numbers.
+const NUMERIC_FIELDS: [&str; 7] = ["index", "pid", "count", "x", "y", "width", "height"];
+
+impl WindowFormatter {
+    // XML format - list
+    pub(crate) fn format_xml(windows: &[WindowInfo], config: &FormatConfig) -> String {
+        let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<windows");
+        push_attribute(&mut output, "count", &windows.len().to_string());
+        if config.snapshot {
+            let header = Self::snapshot_header(windows.len(), config);
+            for (name, value) in header.attributes() {
+                push_attribute(&mut output, name, &value);
+            }
+        }
+        if windows.is_empty() {
+            output.push_str("/>\n");
+            return output;
+        }
+        output.push_str(">\n");
+
+        for window in windows {
+            write_window(&mut output, window, config);
+        }
+
+        output.push_str("</windows>\n");
+        output
+    }
+}
+
+fn write_window(output: &mut String, window: &WindowInfo, config: &FormatConfig) {
+    let layout = config.xml.layout;
+    let p = &window.position;
+    // (template field, XML name, value)
+    let fields = [
+        ("index", "index", window.index.to_string()),
+        ("hwnd", "hwnd", format!("0x{:x}", window.hwnd)),
+        ("pid", "pid", window.pid.to_string()),
+        ("title", "title", window.title.clone()),
+        ("class", "class_name", window.class_name.clone()),
+        ("process", "process_name", window.process_name.clone()),
+        (
+            "file",
+            "process_file",
+            window.process_file.to_string_lossy().into_owned(),
+        ),
+    ];
+    let position = [p.x, p.y, p.width, p.height];
+    let kept = |field: &str| !config.redaction.drops(field);
+    let fields: Vec<_> = fields
+        .into_iter()
+        .filter(|(field, ..)| kept(field))
+        .collect();
+    let position: Vec<_> = POSITION_FIELDS
+        .into_iter()
+        .zip(position.map(|value| value.to_string()))
+        .filter(|(field, _)| kept(field))
+        .collect();
+
+    let is_attribute = |name: &str| match layout {
+        XmlLayout::Mixed => name == "index" || name == "hwnd",
+        XmlLayout::Attributes => true,
+        XmlLayout::Elements => false,
+    };
+
+    output.push_str("  <window");
+    for (_, name, value) in fields.iter().filter(|(_, name, _)| is_attribute(name)) {
+        push_attribute(output, name, value);
+    }
+    if layout == XmlLayout::Attributes {
+        for (name, value) in &position {
+            push_attribute(output, name, value);
+        }
+        output.push_str("/>\n");
+        return;
+    }
+    output.push_str(">\n");
+
+    for (_, name, value) in fields.iter().filter(|(_, name, _)| !is_attribute(name)) {
+        push_element(output, "    ", name, value);
+    }
+    if !position.is_empty() {
+        output.push_str("    <position");
+        if layout == XmlLayout::Mixed {
+            for (name, value) in &position {
+                push_attribute(output, name, value);
+            }
+            output.push_str("/>\n");
+        } else {
+            output.push_str(">\n");
+            for (name, value) in &position {
+                push_element(output, "      ", name, value);
+            }
+            output.push_str("    </position>\n");
+        }
+    }
+    output.push_str("  </window>\n");
+}
+
+fn push_attribute(output: &mut String, name: &str, value: &str) {
+    output.push_str(&format!(" {}=\"{}\"", name, escape_xml(value, true)));
+}
+
+fn push_element(output: &mut String, indent: &str, name: &str, value: &str) {
+    if value.is_empty() {
+        output.push_str(&format!("{}<{}/>\n", indent, name));
+    } else {
+        output.push_str(&format!(
+            "{}<{}>{}</{}>\n",
+            indent,
+            name,
+            escape_xml(value, false),
+            name
+        ));
+    }
+}
+
+// Characters allowed in XML 1.0 documents
+fn is_xml_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{d7ff}' | '\u{e000}'..='\u{fffd}' | '\u{10000}'..)
+}
+
+/// Escape markup characters. Characters XML 1.0 cannot represent at all, such
+/// as ESC, are written as `\x1b`-style escapes. Backslashes are left alone so
+/// Windows paths stay readable, which makes these escapes one-way: the reader
+/// keeps them as text. In attributes, tabs and line breaks become character
+/// references so parsers do not normalize them to spaces.
+pub(crate) fn escape_xml(text: &str, attribute: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            '\t' | '\n' if attribute => escaped.push_str(&format!("&#{};", c as u32)),
+            '\r' => escaped.push_str("&#13;"),
+            c if !is_xml_char(c) && (c as u32) < 0x80 => {
+                escaped.push_str(&format!("\\x{:02x}", c as u32))
+            }
+            c if !is_xml_char(c) => escaped.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// An element of a parsed XML document.
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+/// Read a `<windows>` document written by [`OutputFormat::Xml`](crate::OutputFormat::Xml)
+/// in any layout, as a snapshot envelope.
+pub(crate) fn parse_xml(text: &str) -> Result<Value, FormatError> {
+    let mut reader = Reader { rest: text };
+    reader.skip_misc()?;
+    let root = reader.element()?;
+    reader.skip_misc()?;
+    if !reader.rest.is_empty() {
+        return Err(xml_error("content after the root element"));
+    }
+    if root.name != "windows" {
+        return Err(xml_error(&format!(
+            "expected <windows>, found <{}>",
+            root.name
+        )));
+    }
+
+    let mut envelope = Map::new();
+    for (name, value) in root.attributes {
+        envelope.insert(name.clone(), typed(&name, value));
+    }
+    let windows = root
+        .children
+        .into_iter()
+        .filter(|child| child.name == "window")
+        .map(window_value)
+        .collect();
+    envelope.insert("windows".to_string(), Value::Array(windows));
+    Ok(Value::Object(envelope))
+}
+
+fn window_value(element: Element) -> Value {
+    let mut window = Map::new();
+    let mut position = Map::new();
+    let mut insert = |name: String, value: String| {
+        let value = typed(&name, value);
+        if POSITION_FIELDS.contains(&name.as_str()) {
+            position.insert(name, value);
+        } else {
+            window.insert(name, value);
+        }
+    };
+
+    for (name, value) in element.attributes {
+        insert(name, value);
+    }
+    for child in element.children {
+        if child.name == "position" {
+            for (name, value) in child.attributes {
+                insert(name, value);
+            }
+            for field in child.children {
+                insert(field.name, field.text);
+            }
+        } else {
+            insert(child.name, child.text);
+        }
+    }
+
+    if !position.is_empty() {
+        window.insert("position".to_string(), Value::Object(position));
+    }
+    Value::Object(window)
+}
+
+// Numbers for numeric fields, strings otherwise
+fn typed(name: &str, value: String) -> Value {
+    if NUMERIC_FIELDS.contains(&name) || name == "schema_version" {
+        if let Ok(number) = value.trim().parse::<i64>() {
+            return Value::from(number);
+        }
+    }
+    Value::String(value)
+}
+
+fn xml_error(message: &str) -> FormatError {
+    FormatError::XmlError {
+        message: message.to_string(),
+    }
+}
+
+/// Minimal reader for the XML subset written by this crate: elements,
+/// attributes, text, CDATA, comments and the XML declaration.
+struct Reader<'a> {
+    rest: &'a str,
+}
+
+impl Reader<'_> {
+    // Skip whitespace, comments, processing instructions and doctypes
+    fn skip_misc(&mut self) -> Result<(), FormatError> {
+        loop {
+            self.rest = self
+                .rest
+                .trim_start_matches(['\u{feff}', ' ', '\t', '\r', '\n']);
+            let end = if self.rest.starts_with("<?") {
+                "?>"
+            } else if self.rest.starts_with("<!--") {
+                "-->"
+            } else if self.rest.starts_with("<!") {
+                ">"
+            } else {
+                return Ok(());
+            };
+            self.skip_past(end)?;
+        }
+    }
+
+    fn skip_past(&mut self, end: &str) -> Result<&str, FormatError> {
+        let index = self
+            .rest
+            .find(end)
+            .ok_or_else(|| xml_error(&format!("missing `{}`", end)))?;
+        let skipped = &self.rest[..index];
+        self.rest = &self.rest[index + end.len()..];
+        Ok(skipped)
+    }
+
+    fn name(&mut self) -> Result<String, FormatError> {
+        let end = self
+            .rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '='))
+            .unwrap_or(self.rest.len());
+        if end == 0 {
+            return Err(xml_error("expected a name"));
+        }
+        let name = self.rest[..end].to_string();
+        self.rest = &self.rest[end..];
+        Ok(name)
+    }
+
+    fn element(&mut self) -> Result<Element, FormatError> {
+        self.rest = self
+            .rest
+            .strip_prefix('<')
+            .ok_or_else(|| xml_error("expected an element"))?;
+        let mut element = Element {
+            name: self.name()?,
+            attributes: Vec::new(),
+            children: Vec::new(),
+            text: String::new(),
+        };
+
+        loop {
+            self.rest = self.rest.trim_start();
+            if let Some(rest) = self.rest.strip_prefix("/>") {
+                self.rest = rest;
+                return Ok(element);
+            }
+            if let Some(rest) = self.rest.strip_prefix('>') {
+                self.rest = rest;
+                break;
+            }
+            let name = self.name()?;
+            self.rest = self.rest.trim_start();
+            self.rest = self
+                .rest
+                .strip_prefix('=')
+                .ok_or_else(|| xml_error(&format!("attribute `{}` has no value", name)))?
+                .trim_start();
+            let quote = match self.rest.chars().next() {
+                Some(quote @ ('"' | '\'')) => quote,
+                _ => return Err(xml_error(&format!("attribute `{}` is not quoted", name))),
+            };
+            self.rest = &self.rest[1..];
+            let value = unescape(self.skip_past(&quote.to_string())?)?;
+            element.attributes.push((name, value));
+        }
+
+        loop {
+            if let Some(rest) = self.rest.strip_prefix("</") {
+                self.rest = rest;
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(xml_error(&format!("</{}> closes <{}>", name, element.name)));
+                }
+                self.skip_past(">")?;
+                return Ok(element);
+            } else if self.rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if let Some(rest) = self.rest.strip_prefix("<![CDATA[") {
+                self.rest = rest;
+                let text = self.skip_past("]]>")?;
+                element.text.push_str(text);
+            } else if self.rest.starts_with('<') {
+                element.children.push(self.element()?);
+            } else if self.rest.is_empty() {
+                return Err(xml_error(&format!("<{}> is not closed", element.name)));
+            } else {
+                let end = self.rest.find('<').unwrap_or(self.rest.len());
+                element.text.push_str(&unescape(&self.rest[..end])?);
+                self.rest = &self.rest[end..];
+            }
+        }
+    }
+}
+
+// Replace entity and character references; line breaks are normalized to `\n`
+fn unescape(text: &str) -> Result<String, FormatError> {
+    let text = text.replace("\r\n", "\n");
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text.as_str();
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find(';')
+            .ok_or_else(|| xml_error("unterminated entity reference"))?;
+        let entity = &rest[start + 1..start + end];
+        let c = match entity {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32)
+                .ok_or_else(|| xml_error(&format!("unknown entity `&{};`", entity)))?,
+        };
+        output.push(c);
+        rest = &rest[start + end + 1..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}